@@ -24,6 +24,35 @@ fn search(quadtree: &QuadTree<usize>) {
     quadtree.search(&Quad::new(x, y, 50.0, 50.0));
 }
 
+fn nearby_areas(n: usize) -> Vec<Quad> {
+    let x = map_from_01(random(), -450.0, 450.0);
+    let y = map_from_01(random(), -450.0, 450.0);
+    (0..n).map(|_| Quad::new(x + map_from_01(random(), -50.0, 50.0), y + map_from_01(random(), -50.0, 50.0), 5.0, 5.0)).collect()
+}
+
+fn search_each(quadtree: &QuadTree<usize>, areas: &[Quad]) -> Vec<Vec<(usize, Quad)>> {
+    areas.iter().map(|area| quadtree.search(area)).collect()
+}
+
+fn search_batch(quadtree: &QuadTree<usize>, areas: &[Quad]) -> Vec<Vec<(usize, Quad)>> {
+    quadtree.search_batch(areas)
+}
+
+fn grid_insert(n: usize) {
+    let mut grid = QuadGrid::new(25.0);
+    for i in 0..n {
+        let x = map_from_01(random(), -500.0, 500.0);
+        let y = map_from_01(random(), -500.0, 500.0);
+        grid.insert(i, Quad::new(x, y, 0.5, 0.5));
+    }
+}
+
+fn grid_search(grid: &QuadGrid<usize>) {
+    let x = map_from_01(random(), -500.0, 500.0);
+    let y = map_from_01(random(), -500.0, 500.0);
+    grid.search(&Quad::new(x, y, 50.0, 50.0));
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("insert", |b| b.iter(|| insert(1000)));
 
@@ -35,6 +64,27 @@ fn criterion_benchmark(c: &mut Criterion) {
     }
 
     c.bench_function("search", |b| b.iter(|| search(&quadtree)));
+
+    let mut large = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+    for i in 0..50000 {
+        let x = map_from_01(random(), -500.0, 500.0);
+        let y = map_from_01(random(), -500.0, 500.0);
+        large.insert(i, Quad::new(x, y, 0.5, 0.5));
+    }
+    let areas = nearby_areas(100);
+    c.bench_function("search each", |b| b.iter(|| search_each(&large, &areas)));
+    c.bench_function("search batch", |b| b.iter(|| search_batch(&large, &areas)));
+
+    c.bench_function("grid insert", |b| b.iter(|| grid_insert(1000)));
+
+    let mut grid = QuadGrid::new(25.0);
+    for i in 0..1000 {
+        let x = map_from_01(random(), -500.0, 500.0);
+        let y = map_from_01(random(), -500.0, 500.0);
+        grid.insert(i, Quad::new(x, y, 0.5, 0.5));
+    }
+
+    c.bench_function("grid search", |b| b.iter(|| grid_search(&grid)));
 }
 
 criterion_group!(benches, criterion_benchmark);
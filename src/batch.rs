@@ -0,0 +1,57 @@
+use crate::{Dimension, TNode, Tree};
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> TNode<I, S, D> {
+    ///Search for several areas at once. The indices of the areas still in play are `active[from..]`; those overlapping this node are pushed
+    ///after them for the children to use, and dropped again before returning.
+    fn search_batch_into(&self, areas: &[D], active: &mut Vec<usize>, from: usize, results: &mut [Vec<(I, D)>]) {
+        let len = active.len();
+        for k in from..len {
+            if self.space.overlaps(&areas[active[k]]) {
+                active.push(active[k]);
+            }
+        }
+        if !self.objects.is_empty() {
+            for i in active[len..].iter() {
+                results[*i].extend_from_slice(&self.objects);
+            }
+        }
+        if let Some(nodes) = self.nodes.as_ref().filter(|_| active.len() > len) {
+            for n in nodes.iter() {
+                n.search_batch_into(areas, active, len, results);
+            }
+        }
+        active.truncate(len);
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    ///Search for every area in one walk of the tree, returning the same results as searching for each in turn.
+    pub fn search_batch(&self, areas: &[D]) -> Vec<Vec<(I, D)>> {
+        let mut results = vec![Vec::new(); areas.len()];
+        self.prime.search_batch_into(areas, &mut (0..areas.len()).collect(), 0, &mut results);
+        results
+    }
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use crate::*;
+
+    #[test]
+    fn search_batch() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        for x in -50..50 {
+            for y in -50..50 {
+                quadtree.insert(x * 1009 + y * 1013, Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5));
+            }
+        }
+        let areas = (0..200).map(|i| Quad::new((i % 20) as f32 * 7.0 - 60.0, (i / 20) as f32 * 9.0 - 40.0, 15.0, 10.0)).chain([Quad::new(900.0, 0.0, 1.0, 1.0)]).collect::<Vec<Quad>>();
+        let results = quadtree.search_batch(&areas);
+        assert_eq!(results.len(), areas.len());
+        for (area, result) in areas.iter().zip(results.iter()) {
+            let expected = quadtree.search(area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            assert_eq!(result.iter().map(|(id, _)| *id).collect::<Vec<i32>>(), expected);
+        }
+        assert!(results[200].is_empty());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{enclose, validate::encloses, Dimension, Extents, Node, Tree, Violation, DEFAULT_CAPACITY};
+
+///Bounding volume hierarchy node for static geometry. Built top down, splitting wherever the surface area heuristic says it pays off.
+#[derive(Debug, Clone)]
+pub struct BvhNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    bounds: Option<D>,
+    capacity: u8,
+    nodes: Option<Vec<Self>>,
+    objects: Vec<(I, D)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> BvhNode<I, S, D> {
+    ///Create an empty hierarchy whose leaves hold at most `capacity` items.
+    pub fn new(capacity: u8) -> Self {
+        Self {
+            bounds: None,
+            capacity: capacity.max(1),
+            nodes: None,
+            objects: Vec::new(),
+        }
+    }
+    ///Build a hierarchy over the items in one go.
+    pub fn build(capacity: u8, objects: Vec<(I, D)>) -> Self {
+        let mut node = Self::new(capacity);
+        node.bounds = enclose(objects.iter().map(|(_, o)| o));
+        node.objects = objects;
+        node.split();
+        node
+    }
+    ///The smallest dimension enclosing everything stored below the node. None while the node is empty.
+    pub fn bounds(&self) -> Option<&D> {
+        self.bounds.as_ref()
+    }
+
+    ///Split the leaf in two along the axis and position with the lowest surface area cost, unless keeping it whole is cheaper.
+    fn split(&mut self) {
+        let len = self.objects.len();
+        let bounds = match &self.bounds {
+            Some(bounds) if len > 1 => bounds.clone(),
+            _ => return,
+        };
+        let mut best = (f32::INFINITY, 0, 0);
+        for axis in 0..D::AXES {
+            self.objects.sort_by(|(_, a), (_, b)| a.center(axis).total_cmp(&b.center(axis)));
+            let mut suffix = vec![0.0; len];
+            let mut right: Option<D> = None;
+            for i in (1..len).rev() {
+                let grown = right.map_or(self.objects[i].1.clone(), |r| r.union(&self.objects[i].1));
+                suffix[i] = surface(&grown);
+                right = Some(grown);
+            }
+            let mut left: Option<D> = None;
+            for (i, right) in suffix.iter().enumerate().skip(1) {
+                let grown = left.map_or(self.objects[i - 1].1.clone(), |l| l.union(&self.objects[i - 1].1));
+                let cost = surface(&grown) * i as f32 + right * (len - i) as f32;
+                if cost < best.0 {
+                    best = (cost, axis, i);
+                }
+                left = Some(grown);
+            }
+        }
+        let area = surface(&bounds);
+        let split_cost = match area > 0.0 {
+            true => 1.0 + best.0 / area,
+            false => 1.0,
+        };
+        if len <= self.capacity as usize && split_cost >= len as f32 {
+            return;
+        }
+        let (_, axis, at) = best;
+        self.objects.sort_by(|(_, a), (_, b)| a.center(axis).total_cmp(&b.center(axis)));
+        let second = self.objects.split_off(at);
+        let first = std::mem::take(&mut self.objects);
+        self.nodes = Some(vec![Self::build(self.capacity, first), Self::build(self.capacity, second)]);
+    }
+
+    fn take_objects(&mut self, objects: &mut Vec<(I, D)>) {
+        objects.append(&mut self.objects);
+        if let Some(x) = self.nodes.take() {
+            for mut n in x.into_iter() {
+                n.take_objects(objects);
+            }
+        }
+    }
+
+    fn raycast_into(&self, origin: &[f32], direction: &[f32], max: f32, hits: &mut Vec<(I, D, f32)>) {
+        if !self.bounds.as_ref().and_then(|b| b.ray_interval(origin, direction)).is_some_and(|(near, _)| near <= max) {
+            return;
+        }
+        for (id, o) in self.objects.iter() {
+            if let Some((near, _)) = o.ray_interval(origin, direction).filter(|(near, _)| *near <= max) {
+                hits.push((id.clone(), o.clone(), near));
+            }
+        }
+        if let Some(x) = &self.nodes {
+            for n in x.iter() {
+                n.raycast_into(origin, direction, max, hits);
+            }
+        }
+    }
+
+    ///Closest hit below this node that is nearer than the best one found so far. Nearer children are visited first so farther ones can be skipped.
+    fn raycast_first_into(&self, origin: &[f32], direction: &[f32], best: &mut Option<(I, D, f32)>, max: f32) {
+        let limit = |best: &Option<(I, D, f32)>| best.as_ref().map_or(max, |(_, _, t)| *t);
+        for (id, o) in self.objects.iter() {
+            if let Some((near, _)) = o.ray_interval(origin, direction) {
+                if near <= max && best.as_ref().is_none_or(|(_, _, t)| near < *t) {
+                    *best = Some((id.clone(), o.clone(), near));
+                }
+            }
+        }
+        if let Some(x) = &self.nodes {
+            let mut entries = x
+                .iter()
+                .filter_map(|n| Some((n, n.bounds.as_ref()?.ray_interval(origin, direction)?.0)))
+                .collect::<Vec<(&Self, f32)>>();
+            entries.sort_by(|a, b| a.1.total_cmp(&b.1));
+            for (n, near) in entries {
+                if near <= limit(best) {
+                    n.raycast_first_into(origin, direction, best, max);
+                }
+            }
+        }
+    }
+
+    fn validate_at(&self, depth: usize, violations: &mut Vec<Violation<D>>) -> usize {
+        let space = match &self.bounds {
+            Some(bounds) => bounds.clone(),
+            None => return 0,
+        };
+        for (_, item) in self.objects.iter().filter(|(_, o)| !encloses(&space, o)) {
+            violations.push(Violation::StrayItem { depth, space: space.clone(), item: item.clone() });
+        }
+        let mut found = self.objects.len();
+        if let Some(x) = &self.nodes {
+            if !self.objects.is_empty() {
+                violations.push(Violation::InternalObjects { depth, space: space.clone(), objects: self.objects.len() });
+            }
+            for n in x.iter() {
+                if n.bounds.as_ref().is_some_and(|b| !encloses(&space, b)) {
+                    violations.push(Violation::Bounds { depth: depth + 1, space: space.clone() });
+                }
+                found += n.validate_at(depth + 1, violations);
+            }
+        }
+        found
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for BvhNode<I, S, D> {
+    ///Add an item to the child whose bounds grow the least, splitting the leaf it lands in once it overflows.
+    fn insert(&mut self, id: I, other: D) -> bool {
+        self.bounds = Some(match &self.bounds {
+            Some(bounds) => bounds.union(&other),
+            None => other.clone(),
+        });
+        match &mut self.nodes {
+            Some(x) => {
+                let growth = |n: &Self| n.bounds.as_ref().map_or(0.0, |b| surface(&b.union(&other)) - surface(b));
+                let child = (1..x.len()).fold(0, |best, i| if growth(&x[i]) < growth(&x[best]) { i } else { best });
+                x[child].insert(id, other);
+            }
+            None => {
+                self.objects.push((id, other));
+                if self.objects.len() > self.capacity as usize {
+                    self.split();
+                }
+            }
+        }
+        true
+    }
+    ///Rebuild the hierarchy below the node from scratch with the surface area heuristic.
+    fn subdivide(&mut self) {
+        let mut objects = Vec::new();
+        self.take_objects(&mut objects);
+        *self = Self::build(self.capacity, objects);
+    }
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        if self.bounds.as_ref().is_some_and(|b| b.overlaps(area)) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| o.overlaps(area)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search(area, buffer);
+                }
+            }
+        }
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        if self.bounds.as_ref().is_some_and(overlaps) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| overlaps(o)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search_with(overlaps, buffer);
+                }
+            }
+        }
+    }
+    fn clear(&mut self) {
+        *self = Self::new(self.capacity);
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, violations)
+    }
+}
+
+///Surface area of the dimension's boundary: the perimeter of a square, the surface of a cube and so on.
+fn surface<D: Extents>(d: &D) -> f32 {
+    (0..D::AXES)
+        .map(|skip| (0..D::AXES).filter(|axis| *axis != skip).map(|axis| d.half(axis) * 2.0).product::<f32>() * 2.0)
+        .sum()
+}
+
+///Bounding volume hierarchy representation over any dimension.
+pub type Bvh<I, const S: usize, D> = Tree<I, S, D, BvhNode<I, S, D>>;
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Bvh<I, S, D> {
+    ///Build a hierarchy over all the items at once, which gives a better tree than inserting them one by one.
+    pub fn build(objects: Vec<(I, D)>) -> Self {
+        let count = objects.len();
+        let mut tree = Tree::new_tree(BvhNode::build(DEFAULT_CAPACITY, objects));
+        tree.count = count;
+        tree
+    }
+    ///Every item hit by the ray origin + t * direction with t up to max, ordered by the distance t at which the ray enters them.
+    pub fn raycast(&self, origin: &[f32], direction: &[f32], max: f32) -> Vec<(I, D, f32)> {
+        let mut hits = Vec::new();
+        self.prime.raycast_into(origin, direction, max, &mut hits);
+        hits.sort_by(|a, b| a.2.total_cmp(&b.2));
+        hits
+    }
+    ///The first item hit by the ray origin + t * direction with t up to max.
+    pub fn raycast_first(&self, origin: &[f32], direction: &[f32], max: f32) -> Option<(I, D, f32)> {
+        let mut best = None;
+        self.prime.raycast_first_into(origin, direction, &mut best, max);
+        best
+    }
+}
+
+#[cfg(test)]
+mod bvh_tests {
+    use crate::*;
+
+    #[test]
+    fn bvh() {
+        let mut items = Vec::new();
+        for i in 0..3000 {
+            let x = ((i * 7919) % 1000) as f32 - 500.0;
+            let y = ((i * 104729) % 1000) as f32 - 500.0;
+            let z = ((i * 31) % 100) as f32 - 50.0;
+            let size = (i % 7) as f32 + 0.5;
+            items.push((i, Oct::new(x, y, z, size, size / 2.0, 0.1)));
+        }
+        let mut bvh = Bvh::build(items.clone());
+        assert_eq!(bvh.count(), 3000);
+        assert!(bvh.validate().is_ok());
+
+        let area = Oct::new(0.0, 0.0, 0.0, 60.0, 60.0, 20.0);
+        let mut result = bvh.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+        let mut expected = items.iter().filter(|(_, o)| o.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        result.sort();
+        expected.sort();
+        assert_eq!(result, expected);
+
+        let target = items.iter().find(|(_, o)| o.z == 0.0).unwrap().1;
+        let origin = [-600.0, target.y, 0.0];
+        let direction = [1.0, 0.0, 0.0];
+        let hits = bvh.raycast(&origin, &direction, f32::INFINITY);
+        assert!(!hits.is_empty());
+        let expected = items.iter().filter(|(_, o)| o.ray_interval(&origin, &direction).is_some()).count();
+        assert_eq!(hits.len(), expected);
+        assert!(hits.windows(2).all(|w| w[0].2 <= w[1].2));
+        let first = bvh.raycast_first(&origin, &direction, f32::INFINITY).unwrap();
+        assert_eq!(first.2, hits[0].2);
+        assert!(bvh.raycast_first(&origin, &direction, first.2 - 1.0).is_none());
+
+        bvh.insert(3000, Oct::new(-550.0, target.y, 0.0, 1.0, 1.0, 1.0));
+        assert!(bvh.validate().is_ok());
+        assert_eq!(bvh.raycast_first(&origin, &direction, f32::INFINITY).unwrap().0, 3000);
+    }
+}
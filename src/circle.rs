@@ -0,0 +1,88 @@
+use crate::{Extents, Quad, QuadTree, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+
+///2-dimensional circle, stored in trees subdividing on `Quad` cells.
+#[derive(Debug, Clone, Copy)]
+pub struct Circle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn new(x: f32, y: f32, radius: f32) -> Self {
+        Self { x, y, radius }
+    }
+    pub fn overlaps_circle(&self, other: &Circle) -> bool {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2) <= (self.radius + other.radius).powi(2)
+    }
+}
+
+impl Shape<Quad> for Circle {
+    fn bounds(&self) -> Quad {
+        Quad::new(self.x, self.y, self.radius, self.radius)
+    }
+    ///Exact circle-box test: the box's closest point to the center lies within the radius.
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        cell.distance(&Quad::new(self.x, self.y, 0.0, 0.0)) <= self.radius
+    }
+}
+
+///2-dimensional tree of circles.
+pub type CircleTree<I> = Tree<I, 4, Quad, TNode<I, 4, Quad, Circle>, Circle>;
+
+impl<I: Clone> CircleTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(TNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+    ///Find the circles overlapping the circle.
+    pub fn search_circle(&self, circle: &Circle) -> Vec<(I, Circle)> {
+        self.search(&circle.bounds()).into_iter().filter(|(_, c)| c.overlaps_circle(circle)).collect()
+    }
+}
+
+impl<I: Clone> QuadTree<I> {
+    ///Find the items overlapping the circle.
+    pub fn search_circle(&self, circle: &Circle) -> Vec<(I, Quad)> {
+        self.search(&circle.bounds()).into_iter().filter(|(_, q)| circle.overlaps_query(q)).collect()
+    }
+}
+
+#[cfg(test)]
+mod circle_tests {
+    use crate::{testing::ids, *};
+
+    #[test]
+    fn circletree() {
+        let mut circletree = CircleTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut circles = Vec::new();
+        for i in 0..2000 {
+            let circle = Circle::new(((i * 7919) % 1000) as f32 - 499.7, ((i * 104729) % 1000) as f32 - 499.7, (i % 12) as f32 + 0.5);
+            circletree.insert(i, circle);
+            circles.push((i, circle));
+        }
+        assert!(circletree.validate().is_ok());
+
+        let corner = Quad::new(10.0, 10.0, 3.0, 3.0);
+        let circle = Circle::new(2.5, 2.5, 6.0);
+        assert!(circle.bounds().overlaps(&corner));
+        assert!(!circle.overlaps_cell(&corner));
+
+        let query = Circle::new(-3.0, 7.0, 40.0);
+        let result = circletree.search_circle(&query);
+        assert!(result.iter().all(|(_, c)| c.overlaps_circle(&query)));
+        let expected = circles.iter().filter(|(_, c)| c.overlaps_circle(&query)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        assert!(!expected.is_empty());
+        assert_eq!(ids(result), expected);
+
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        for (id, c) in circles.iter() {
+            quadtree.insert(*id, c.bounds());
+        }
+        let disc = Circle::new(-3.0, 7.0, 150.0);
+        let result = quadtree.search_circle(&disc);
+        assert!(result.iter().all(|(_, q)| disc.overlaps_cell(q)));
+        let expected = circles.iter().filter(|(_, c)| disc.overlaps_cell(&c.bounds())).map(|(id, _)| *id).collect::<Vec<i32>>();
+        assert!(expected.len() < circles.iter().filter(|(_, c)| disc.bounds().overlaps(&c.bounds())).count());
+        assert_eq!(ids(result), expected);
+    }
+}
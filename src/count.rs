@@ -0,0 +1,102 @@
+use crate::{Dimension, Insertion, Shape, TNode, Tree};
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> TNode<I, S, D, T> {
+    ///Whether the node counts the item, which in Duplicate mode only the leaf holding its center does.
+    fn counts(&self, item: &T) -> bool {
+        self.insertion == Insertion::Smallest || self.space.contains_center(&item.bounds())
+    }
+    ///Count the items centered in the area. Nodes it fully covers are answered from their totals.
+    fn count_into(&self, area: &D) -> usize {
+        if self.total == 0 || !self.space.overlaps(area) {
+            return 0;
+        }
+        let covered = self.space.within(area);
+        if covered && self.insertion == Insertion::Duplicate {
+            return self.total as usize;
+        }
+        let own = self.objects.iter().filter(|(_, o)| self.counts(o) && area.contains_center(&o.bounds())).count();
+        own + self.nodes.iter().flat_map(|x| x.iter()).map(|n| if covered { n.total as usize } else { n.count_into(area) }).sum::<usize>()
+    }
+    ///Whether any item is centered in the area, stopping at the first one found.
+    fn any_into(&self, area: &D) -> bool {
+        if self.total == 0 || !self.space.overlaps(area) {
+            return false;
+        }
+        let covered = self.space.within(area);
+        if covered && self.insertion == Insertion::Duplicate {
+            return true;
+        }
+        self.objects.iter().any(|(_, o)| self.counts(o) && area.contains_center(&o.bounds()))
+            || self.nodes.iter().flat_map(|x| x.iter()).any(|n| if covered { n.total > 0 } else { n.any_into(area) })
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> Tree<I, S, D, TNode<I, S, D, T>, T> {
+    ///Number of items whose center lies in the area, counting its lower edges but not its upper ones, found without collecting them.
+    ///Each item is counted once as `count` does, and only by its center: an item overlapping the area with its center outside is left out.
+    pub fn count_centered_in(&self, area: &D) -> usize {
+        self.prime.count_into(area)
+    }
+    ///Whether any item's center lies in the area, by the same rule as `count_centered_in`, found without collecting them.
+    pub fn any_centered_in(&self, area: &D) -> bool {
+        self.prime.any_into(area)
+    }
+}
+
+#[cfg(test)]
+mod count_tests {
+    use crate::*;
+
+    #[test]
+    fn count_centered_in() {
+        let root = Quad::new(0.0, 0.0, 500.0, 500.0);
+        let mut duplicate = QuadTree::new(root);
+        let mut smallest = QuadTree::new_tree(TNode::with_insertion(root, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH, Insertion::Smallest));
+        let mut items = Vec::new();
+        for x in -50..50 {
+            for y in -50..50 {
+                let item = Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5);
+                duplicate.insert(x * 1009 + y * 1013, item);
+                smallest.insert(x * 1009 + y * 1013, item);
+                items.push(item);
+            }
+        }
+        for i in 0..500 {
+            let item = Quad::new(((i * 7919) % 1000) as f32 - 499.7, ((i * 104729) % 1000) as f32 - 499.7, (i % 25) as f32 + 0.5, (i % 10) as f32 + 0.5);
+            duplicate.insert(20000 + i, item);
+            smallest.insert(20000 + i, item);
+            items.push(item);
+        }
+        assert!(duplicate.validate().is_ok());
+        assert!(smallest.validate().is_ok());
+        assert_eq!(duplicate.count_centered_in(&root), 10500);
+        assert_eq!(duplicate.count_centered_in(&root), duplicate.count());
+
+        let grid = |area: &Quad| items[..10000].iter().filter(|o| area.contains_center(o)).count();
+        assert_eq!(grid(&root), 10000);
+        assert_eq!(grid(&Quad::new(-250.0, -250.0, 250.0, 250.0)), 2500);
+        let areas = [
+            root,
+            Quad::new(-250.0, -250.0, 250.0, 250.0),
+            Quad::new(0.0, 0.0, 250.0, 250.0),
+            Quad::new(13.0, -71.0, 140.0, 90.0),
+            Quad::new(300.0, 300.0, 0.1, 0.1),
+            Quad::new(900.0, 0.0, 10.0, 10.0),
+        ];
+        for area in areas {
+            let expected = items.iter().filter(|o| area.contains_center(o)).count();
+            assert_eq!(duplicate.count_centered_in(&area), expected);
+            assert_eq!(smallest.count_centered_in(&area), expected);
+            assert_eq!(duplicate.any_centered_in(&area), expected > 0);
+            assert_eq!(smallest.any_centered_in(&area), expected > 0);
+        }
+
+        let mut quadtree = QuadTree::new(root);
+        quadtree.insert(0, Quad::new(0.0, 0.0, 100.0, 100.0));
+        let corner = Quad::new(50.0, 50.0, 5.0, 5.0);
+        assert_eq!(quadtree.search(&corner).len(), 1);
+        assert_eq!(quadtree.count_centered_in(&corner), 0);
+        assert!(!quadtree.any_centered_in(&corner));
+        assert!(quadtree.any_centered_in(&Quad::new(5.0, 5.0, 5.0, 5.0)));
+    }
+}
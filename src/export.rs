@@ -0,0 +1,110 @@
+use crate::{Dimension, TNode, Tree};
+use std::fmt::Write;
+
+struct Entry<'a, I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    node: &'a TNode<I, S, D>,
+    depth: usize,
+    children: Vec<usize>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    ///Dump the node hierarchy as a Graphviz DOT graph. Nodes are numbered in depth first order.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph tree {\n    node [shape=box];\n");
+        for (index, entry) in self.entries().iter().enumerate() {
+            let bounds = (0..D::AXES)
+                .map(|axis| format!("[{}, {}]", entry.node.space.min(axis), entry.node.space.max(axis)))
+                .collect::<Vec<String>>()
+                .join(" x ");
+            let _ = writeln!(
+                dot,
+                "    n{} [label=\"depth {}\\nobjects {}\\n{}\"];",
+                index,
+                entry.depth,
+                entry.node.objects.len(),
+                bounds,
+            );
+            for child in entry.children.iter() {
+                let _ = writeln!(dot, "    n{} -> n{};", index, child);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    ///Dump the node hierarchy as a JSON document. Nodes are numbered in depth first order and refer to their children by index.
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"count\":{},\"nodes\":[", self.count);
+        for (index, entry) in self.entries().iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let min = (0..D::AXES).map(|axis| number(entry.node.space.min(axis))).collect::<Vec<String>>();
+            let max = (0..D::AXES).map(|axis| number(entry.node.space.max(axis))).collect::<Vec<String>>();
+            let children = entry.children.iter().map(|c| c.to_string()).collect::<Vec<String>>();
+            let _ = write!(
+                json,
+                "{{\"index\":{},\"depth\":{},\"objects\":{},\"min\":[{}],\"max\":[{}],\"children\":[{}]}}",
+                index,
+                entry.depth,
+                entry.node.objects.len(),
+                min.join(","),
+                max.join(","),
+                children.join(","),
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+
+    fn entries(&self) -> Vec<Entry<'_, I, S, D>> {
+        let mut entries = Vec::new();
+        flatten(&self.prime, 0, &mut entries);
+        entries
+    }
+}
+
+fn flatten<'a, I: Clone, const S: usize, D: Clone + Dimension<S>>(node: &'a TNode<I, S, D>, depth: usize, entries: &mut Vec<Entry<'a, I, S, D>>) -> usize {
+    let index = entries.len();
+    entries.push(Entry { node, depth, children: Vec::new() });
+    if let Some(nodes) = &node.nodes {
+        let children = nodes.iter().map(|n| flatten(n, depth + 1, entries)).collect();
+        entries[index].children = children;
+    }
+    index
+}
+
+///JSON has no representation for infinities or NaN.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("null")
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use crate::*;
+
+    #[test]
+    fn dot_and_json() {
+        let mut octtree = OctTree::new(Oct::new(0.0, 0.0, 0.0, 500.0, 500.0, 500.0));
+        for x in -5..5 {
+            for y in -5..5 {
+                octtree.insert(x * 1009 + y * 1013, Oct::new(x as f32 * 100.0 + 2.5, y as f32 * 100.0 + 2.5, 2.5, 0.5, 0.5, 0.5));
+            }
+        }
+        let stats = octtree.stats();
+
+        let dot = octtree.to_dot();
+        assert!(dot.starts_with("digraph tree {"));
+        assert!(dot.contains("n0 [label=\"depth 0\\nobjects 0\\n[-500, 500] x [-500, 500] x [-500, 500]\"];"));
+        assert_eq!(dot.matches("[label=").count(), stats.nodes);
+        assert_eq!(dot.matches(" -> ").count(), stats.nodes - 1);
+
+        let json = octtree.to_json();
+        assert!(json.starts_with("{\"count\":100,\"nodes\":[{\"index\":0,\"depth\":0,\"objects\":0,\"min\":[-500,-500,-500],\"max\":[500,500,500],\"children\":[1,"));
+        assert_eq!(json.matches("\"index\":").count(), stats.nodes);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{Dimension, Insertion, TNode, Tree};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+struct FrozenNode<D> {
+    space: D,
+    count: u32,
+    depth_limit: u8,
+    ///Index of the first of this node's children, which are stored next to each other. Zero for leaves.
+    children: u32,
+    start: u32,
+    end: u32,
+}
+
+///Read-only tree with its nodes and items packed into two contiguous arrays.
+#[derive(Debug, Clone)]
+pub struct FrozenTree<I, const S: usize, D: Dimension<S>> {
+    nodes: Vec<FrozenNode<D>>,
+    items: Vec<(I, D)>,
+    capacity: u8,
+    insertion: Insertion,
+    count: usize,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> FrozenTree<I, S, D> {
+    pub fn search(&self, area: &D) -> Vec<(I, D)> {
+        let mut buffer = Vec::with_capacity(self.count);
+        self.search_node(0, &|space: &D| space.overlaps(area), &mut buffer);
+        buffer
+    }
+    pub fn search_custom<OF>(&self, overlaps: &OF) -> Vec<(I, D)>
+    where
+        OF: Fn(&D) -> bool,
+    {
+        let mut buffer = Vec::with_capacity(self.count);
+        self.search_node(0, overlaps, &mut buffer);
+        buffer
+    }
+    ///Rebuild a mutable tree with the same layout.
+    pub fn thaw(&self) -> Tree<I, S, D, TNode<I, S, D>> {
+        let mut tree = Tree::new_tree(self.thaw_node(0));
+        tree.count = self.count;
+        tree
+    }
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn search_node<OF>(&self, index: usize, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        let node = &self.nodes[index];
+        if overlaps(&node.space) {
+            buffer.extend_from_slice(&self.items[node.start as usize..node.end as usize]);
+            if node.children > 0 {
+                let first = node.children as usize;
+                for i in first..first + S {
+                    self.search_node(i, overlaps, buffer);
+                }
+            }
+        }
+    }
+    fn thaw_node(&self, index: usize) -> TNode<I, S, D> {
+        let node = &self.nodes[index];
+        let nodes: Option<Box<Vec<TNode<I, S, D>>>> = match node.children as usize {
+            0 => None,
+            first => Some(Box::new((first..first + S).map(|i| self.thaw_node(i)).collect())),
+        };
+        TNode {
+            space: node.space.clone(),
+            count: node.count,
+            total: node.count + nodes.iter().flat_map(|x| x.iter()).map(|n| n.total).sum::<u32>(),
+            capacity: self.capacity,
+            depth_limit: node.depth_limit,
+            insertion: self.insertion,
+            nodes,
+            objects: self.items[node.start as usize..node.end as usize].to_vec(),
+        }
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    ///Pack the tree into a flat layout for fast read-only queries. Nodes are stored breadth first so siblings sit next to each other.
+    pub fn freeze(&self) -> FrozenTree<I, S, D> {
+        let mut nodes = Vec::new();
+        let mut items = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(&self.prime);
+        while let Some(node) = queue.pop_front() {
+            let start = items.len() as u32;
+            items.extend_from_slice(&node.objects);
+            let children = match &node.nodes {
+                Some(x) => {
+                    let first = nodes.len() + queue.len() + 1;
+                    queue.extend(x.iter());
+                    first as u32
+                }
+                None => 0,
+            };
+            nodes.push(FrozenNode {
+                space: node.space.clone(),
+                count: node.count,
+                depth_limit: node.depth_limit,
+                children,
+                start,
+                end: items.len() as u32,
+            });
+        }
+        FrozenTree {
+            nodes,
+            items,
+            capacity: self.prime.capacity,
+            insertion: self.prime.insertion,
+            count: self.count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod frozen_tests {
+    use crate::*;
+
+    #[test]
+    fn freeze_and_thaw() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        for x in -50..50 {
+            for y in -50..50 {
+                quadtree.insert(x * 1009 + y * 1013, Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5));
+            }
+        }
+        let area = Quad::new(0.0, 0.0, 50.0, 50.0);
+        let frozen = quadtree.freeze();
+        assert_eq!(frozen.count(), quadtree.count());
+        assert_eq!(frozen.search(&area).len(), 169);
+        assert_eq!(frozen.search_custom(&|space: &Quad| space.overlaps(&area)).len(), 169);
+
+        let mut thawed = frozen.thaw();
+        assert_eq!(thawed.search(&area).len(), 169);
+        thawed.insert(0, Quad::new(1.0, 1.0, 0.5, 0.5));
+        assert_eq!(thawed.count(), quadtree.count() + 1);
+        assert_eq!(thawed.search(&area).len(), 170);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{Dimension, Oct, OctTree, TNode};
+
+///Where a box lies relative to a frustum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    Inside,
+    Intersecting,
+    Outside,
+}
+
+///Camera frustum bounded by six planes. Each plane is `[a, b, c, d]` with a unit normal pointing inwards, so points with `a*x + b*y + c*z + d >= 0` are on the inner side.
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    pub planes: [[f32; 4]; 6],
+}
+
+impl Frustum {
+    ///Normalizes the planes, which should have their normals pointing inwards.
+    pub fn new(planes: [[f32; 4]; 6]) -> Self {
+        Self {
+            planes: planes.map(|[a, b, c, d]| {
+                let length = (a * a + b * b + c * c).sqrt();
+                [a / length, b / length, c / length, d / length]
+            }),
+        }
+    }
+    ///Extract the planes of a view-projection matrix given as rows, which maps column vectors to clip space with depth running from -w to w.
+    ///The planes come out in the order left, right, bottom, top, near, far.
+    pub fn from_matrix(m: [[f32; 4]; 4]) -> Self {
+        let combine = |row: usize, sign: f32| [0, 1, 2, 3].map(|col| m[3][col] + sign * m[row][col]);
+        Self::new([combine(0, 1.0), combine(0, -1.0), combine(1, 1.0), combine(1, -1.0), combine(2, 1.0), combine(2, -1.0)])
+    }
+    pub fn classify(&self, area: &Oct) -> Classification {
+        let mut classification = Classification::Inside;
+        for [a, b, c, d] in self.planes {
+            let reach = area.half_x * a.abs() + area.half_y * b.abs() + area.half_z * c.abs();
+            let distance = a * area.x + b * area.y + c * area.z + d;
+            if distance < -reach {
+                return Classification::Outside;
+            }
+            if distance < reach {
+                classification = Classification::Intersecting;
+            }
+        }
+        classification
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> TNode<I, S, D> {
+    ///Collect the items the filter keeps from the nodes the classifier doesn't rule out, passing along how their node was classified.
+    ///Nodes below a fully inside one are not classified.
+    pub(crate) fn cull<CF, KF>(&self, classify: &CF, keeps: &KF, buffer: &mut Vec<(I, D)>)
+    where
+        CF: Fn(&D) -> Classification,
+        KF: Fn(&Self, &D, Classification) -> bool,
+    {
+        match classify(&self.space) {
+            Classification::Outside => {}
+            Classification::Inside => self.visit(0, &mut |n, _| buffer.extend(n.objects.iter().filter(|(_, o)| keeps(n, o, Classification::Inside)).cloned())),
+            Classification::Intersecting => {
+                buffer.extend(self.objects.iter().filter(|(_, o)| keeps(self, o, Classification::Intersecting)).cloned());
+                if let Some(nodes) = &self.nodes {
+                    for n in nodes.iter() {
+                        n.cull(classify, keeps, buffer);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I: Clone> OctTree<I> {
+    ///Find the items inside or intersecting the frustum. Items of fully inside subtrees are taken without testing them.
+    pub fn frustum_query(&self, frustum: &Frustum) -> Vec<(I, Oct)> {
+        let mut buffer = Vec::new();
+        self.prime.cull(&|d| frustum.classify(d), &|_, o, node| node == Classification::Inside || frustum.classify(o) != Classification::Outside, &mut buffer);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod frustum_tests {
+    use crate::{testing::ids, *};
+
+    #[test]
+    fn frustum() {
+        let (near, far) = (1.0, 100.0);
+        let frustum = Frustum::from_matrix([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far)],
+            [0.0, 0.0, -1.0, 0.0],
+        ]);
+        assert_eq!(frustum.classify(&Oct::new(0.0, 0.0, -50.0, 1.0, 1.0, 1.0)), Classification::Inside);
+        assert_eq!(frustum.classify(&Oct::new(0.0, 0.0, -1.0, 1.0, 1.0, 1.0)), Classification::Intersecting);
+        assert_eq!(frustum.classify(&Oct::new(0.0, 0.0, 50.0, 1.0, 1.0, 1.0)), Classification::Outside);
+        assert_eq!(frustum.classify(&Oct::new(60.0, 0.0, -50.0, 1.0, 1.0, 1.0)), Classification::Outside);
+
+        let mut octree = OctTree::new(Oct::new(0.0, 0.0, 0.0, 128.0, 128.0, 128.0));
+        let mut items = Vec::new();
+        for x in -20..20 {
+            for y in -20..20 {
+                for z in -20..20 {
+                    let item = Oct::new(x as f32 * 6.0 + 1.3, y as f32 * 6.0 + 1.3, z as f32 * 6.0 + 1.3, 0.5, 0.5, 0.5);
+                    octree.insert((x, y, z), item);
+                    items.push(((x, y, z), item));
+                }
+            }
+        }
+        let result = ids(octree.frustum_query(&frustum));
+        let reached = items.iter().filter(|(_, o)| frustum.classify(o) != Classification::Outside).map(|(id, _)| *id).collect::<Vec<_>>();
+        let inside = items.iter().filter(|(_, o)| frustum.classify(o) == Classification::Inside).count();
+        assert!(inside > 0 && inside < reached.len());
+        assert_eq!(result, reached);
+    }
+}
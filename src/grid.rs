@@ -0,0 +1,233 @@
+use crate::{Dimension, Node, Oct, Quad, Tree, Violation};
+use std::collections::HashMap;
+
+///Most cells an item is referenced from. Larger items are kept in a separate list that every search checks.
+const MAX_CELLS: f64 = 4096.0;
+
+///Flat grid of equally sized cells, backed by a hash map so only occupied cells take up memory.
+///Items are stored once and referenced from every cell they overlap. Supports dimensions with up to 4 axes.
+#[derive(Debug, Clone)]
+pub struct GridNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    cell: f32,
+    cells: HashMap<[i32; 4], Vec<u32>>,
+    large: Vec<u32>,
+    objects: Vec<(I, D)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> GridNode<I, S, D> {
+    ///Create an empty grid whose cells are `cell` long along every axis.
+    pub fn new(cell: f32) -> Self {
+        assert!(D::AXES <= 4, "A grid supports at most 4 axes");
+        assert!(cell.is_finite() && cell > 0.0, "A grid cell must have a finite, positive size");
+        Self {
+            cell,
+            cells: HashMap::new(),
+            large: Vec::new(),
+            objects: Vec::new(),
+        }
+    }
+    pub fn cell_size(&self) -> f32 {
+        self.cell
+    }
+    ///Number of cells holding at least one item.
+    pub fn occupied(&self) -> usize {
+        self.cells.len()
+    }
+
+    ///Lowest and highest cell coordinates the dimension touches along every axis.
+    fn range(&self, d: &D) -> ([i32; 4], [i32; 4]) {
+        let mut low = [0; 4];
+        let mut high = [0; 4];
+        for axis in 0..D::AXES {
+            low[axis] = (d.min(axis) / self.cell).floor() as i32;
+            high[axis] = (d.max(axis) / self.cell).floor() as i32;
+        }
+        (low, high)
+    }
+
+    ///Number of cells in the range, which is NaN when the dimension isn't finite.
+    fn span((low, high): &([i32; 4], [i32; 4]), d: &D) -> f64 {
+        if (0..D::AXES).all(|axis| d.min(axis).is_finite() && d.max(axis).is_finite()) {
+            (0..D::AXES).map(|axis| (high[axis] as i64 - low[axis] as i64 + 1) as f64).product()
+        } else {
+            f64::NAN
+        }
+    }
+
+    ///Whether the dimension covers too many cells to be referenced from each of them.
+    fn oversized(range: &([i32; 4], [i32; 4]), d: &D) -> bool {
+        let span = Self::span(range, d);
+        span.is_nan() || span > MAX_CELLS
+    }
+
+    fn space(&self, key: &[i32; 4]) -> D {
+        let center = (0..D::AXES).map(|axis| (key[axis] as f32 + 0.5) * self.cell).collect::<Vec<f32>>();
+        let half = vec![self.cell / 2.0; D::AXES];
+        D::from_extents(&center, &half)
+    }
+
+    ///Indices of the objects referenced by the cells the area touches, without repeats.
+    fn gather(&self, area: &D) -> Vec<u32> {
+        let (low, high) = self.range(area);
+        let span = Self::span(&(low, high), area);
+        let inside = |key: &[i32; 4]| (0..D::AXES).all(|axis| key[axis] >= low[axis] && key[axis] <= high[axis]);
+        let mut indices = Vec::new();
+        if span.is_nan() || span > self.cells.len() as f64 {
+            for (_, cell) in self.cells.iter().filter(|(key, _)| inside(key)) {
+                indices.extend_from_slice(cell);
+            }
+        } else {
+            let mut key = low;
+            'cells: loop {
+                if let Some(cell) = self.cells.get(&key) {
+                    indices.extend_from_slice(cell);
+                }
+                for axis in 0..D::AXES {
+                    if key[axis] < high[axis] {
+                        key[axis] += 1;
+                        continue 'cells;
+                    }
+                    key[axis] = low[axis];
+                }
+                break;
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for GridNode<I, S, D> {
+    fn insert(&mut self, id: I, other: D) -> bool {
+        let index = self.objects.len() as u32;
+        let (low, high) = self.range(&other);
+        if Self::oversized(&(low, high), &other) {
+            self.large.push(index);
+            self.objects.push((id, other));
+            return true;
+        }
+        let mut key = low;
+        'cells: loop {
+            self.cells.entry(key).or_default().push(index);
+            for axis in 0..D::AXES {
+                if key[axis] < high[axis] {
+                    key[axis] += 1;
+                    continue 'cells;
+                }
+                key[axis] = low[axis];
+            }
+            break;
+        }
+        self.objects.push((id, other));
+        true
+    }
+    ///Grid cells have a fixed size, so there is nothing to subdivide.
+    fn subdivide(&mut self) {}
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        for index in self.gather(area).into_iter().chain(self.large.iter().copied()) {
+            let object = &self.objects[index as usize];
+            if object.1.overlaps(area) {
+                buffer.push(object.clone());
+            }
+        }
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        let mut indices = Vec::new();
+        for (key, cell) in self.cells.iter() {
+            if overlaps(&self.space(key)) {
+                indices.extend_from_slice(cell);
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        for index in indices.into_iter().chain(self.large.iter().copied()) {
+            let object = &self.objects[index as usize];
+            if overlaps(&object.1) {
+                buffer.push(object.clone());
+            }
+        }
+    }
+    fn clear(&mut self) {
+        self.cells.clear();
+        self.large.clear();
+        self.objects.clear();
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        let mut references = vec![0; self.objects.len()];
+        for (key, cell) in self.cells.iter() {
+            let space = self.space(key);
+            for index in cell.iter() {
+                match self.objects.get(*index as usize) {
+                    Some((_, item)) if space.overlaps(item) => references[*index as usize] += 1,
+                    Some((_, item)) => violations.push(Violation::StrayItem { depth: 0, space: space.clone(), item: item.clone() }),
+                    None => violations.push(Violation::Bounds { depth: 0, space: space.clone() }),
+                }
+            }
+        }
+        for (i, (_, item)) in self.objects.iter().enumerate() {
+            let range = self.range(item);
+            let cells = if Self::oversized(&range, item) { 0 } else { Self::span(&range, item) as usize };
+            if references[i] != cells {
+                violations.push(Violation::NodeCount { depth: 0, space: item.clone(), expected: cells, found: references[i] });
+            }
+        }
+        self.objects.len()
+    }
+}
+
+///2-dimensional grid representation.
+pub type QuadGrid<I> = Tree<I, 4, Quad, GridNode<I, 4, Quad>>;
+
+impl<I: Clone> QuadGrid<I> {
+    pub fn new(cell: f32) -> Self {
+        Tree::new_tree(GridNode::new(cell))
+    }
+}
+
+///3-dimensional grid representation.
+pub type OctGrid<I> = Tree<I, 8, Oct, GridNode<I, 8, Oct>>;
+
+impl<I: Clone> OctGrid<I> {
+    pub fn new(cell: f32) -> Self {
+        Tree::new_tree(GridNode::new(cell))
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use crate::*;
+
+    #[test]
+    fn quadgrid() {
+        let mut grid = QuadGrid::new(25.0);
+        let mut items = Vec::new();
+        for i in 0..2000 {
+            let x = ((i * 7919) % 1000) as f32 - 500.0;
+            let y = ((i * 104729) % 1000) as f32 - 500.0;
+            let item = Quad::new(x, y, (i % 40) as f32 + 0.5, (i % 30) as f32 + 0.5);
+            grid.insert(i, item);
+            items.push((i, item));
+        }
+        grid.insert(2000, Quad::new(0.0, 0.0, 1.0e9, 1.0e9));
+        items.push((2000, Quad::new(0.0, 0.0, 1.0e9, 1.0e9)));
+        assert_eq!(grid.count(), 2001);
+        assert!(grid.validate().is_ok());
+
+        for area in [Quad::new(0.0, 0.0, 50.0, 50.0), Quad::new(0.0, 0.0, 5000.0, 5000.0)] {
+            let mut result = grid.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            let expected = items.iter().filter(|(_, item)| item.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            result.sort();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_cell() {
+        QuadGrid::<i32>::new(0.0);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    morton::{grid_bits, quantize},
+    Dimension, Extents, Node, Tree,
+};
+
+///Hilbert index of the coordinates, each using the given number of bits, at most 32. Consecutive indices always belong to neighboring cells.
+///Follows Skilling's transposed form: the coordinates are turned into the transposed index in place, then its bits are gathered most significant first.
+pub fn hilbert_encode(coords: &[u32], bits: u32) -> u64 {
+    assert!(bits <= 32, "Hilbert coordinates hold at most 32 bits");
+    assert!(bits as usize * coords.len() <= 64, "A Hilbert index holds at most 64 bits");
+    let mut x = coords.iter().map(|c| *c as u64).collect::<Vec<u64>>();
+    let n = x.len();
+    if bits == 0 || n == 0 {
+        return 0;
+    }
+    let mut q = 1u64 << (bits - 1);
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..n {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+    for i in 1..n {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    let mut q = 1u64 << (bits - 1);
+    while q > 1 {
+        if x[n - 1] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    let mut index = 0;
+    for bit in (0..bits).rev() {
+        for c in x.iter() {
+            index = (index << 1) | (((c ^ t) >> bit) & 1);
+        }
+    }
+    index
+}
+
+///Split a Hilbert index back into its coordinates.
+pub fn hilbert_decode(index: u64, axes: usize, bits: u32) -> Vec<u32> {
+    assert!(bits <= 32, "Hilbert coordinates hold at most 32 bits");
+    assert!(bits as usize * axes <= 64, "A Hilbert index holds at most 64 bits");
+    let mut x = vec![0u64; axes];
+    if bits == 0 || axes == 0 {
+        return vec![0; axes];
+    }
+    let mut position = bits as usize * axes;
+    for bit in (0..bits).rev() {
+        for c in x.iter_mut() {
+            position -= 1;
+            *c |= ((index >> position) & 1) << bit;
+        }
+    }
+    let t = x[axes - 1] >> 1;
+    for i in (1..axes).rev() {
+        x[i] ^= x[i - 1];
+    }
+    x[0] ^= t;
+    let mut q = 2u64;
+    while q != 1u64 << bits {
+        let p = q - 1;
+        for i in (0..axes).rev() {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q <<= 1;
+    }
+    x.into_iter().map(|c| c as u32).collect()
+}
+
+///Hilbert index of the dimension's center on the finest grid laid over the root. Centers outside the root are clamped to its edge.
+pub fn hilbert_index<D: Extents>(root: &D, d: &D) -> u64 {
+    let bits = grid_bits(D::AXES);
+    hilbert_encode(&quantize(root, d, bits), bits)
+}
+
+///Sort the items along the Hilbert curve over the root, so items close in the order are close in space.
+pub fn hilbert_sort<I, D: Extents>(root: &D, items: &mut [(I, D)]) {
+    items.sort_by_cached_key(|(_, d)| hilbert_index(root, d));
+}
+
+///Split the items into contiguous runs of the Hilbert curve over the root. Shard sizes differ by at most one item.
+pub fn hilbert_shards<I, D: Extents>(root: &D, mut items: Vec<(I, D)>, shards: usize) -> Vec<Vec<(I, D)>> {
+    let shards = shards.max(1);
+    hilbert_sort(root, &mut items);
+    let (size, extra) = (items.len() / shards, items.len() % shards);
+    let mut rest = items.into_iter();
+    (0..shards).map(|i| rest.by_ref().take(size + (i < extra) as usize).collect()).collect()
+}
+
+impl<I, const S: usize, D: Dimension<S>, N: Node<I, S, D>> Tree<I, S, D, N> {
+    ///Insert the items in Hilbert order over the root, which keeps neighboring items together as the tree grows. Returns how many were inserted.
+    pub fn bulk_load(&mut self, root: &D, items: impl IntoIterator<Item = (I, D)>) -> usize {
+        let mut items = items.into_iter().collect::<Vec<(I, D)>>();
+        hilbert_sort(root, &mut items);
+        items.into_iter().map(|(id, d)| self.insert(id, d)).filter(|inserted| *inserted).count()
+    }
+}
+
+#[cfg(test)]
+mod hilbert_tests {
+    use crate::*;
+
+    #[test]
+    fn hilbert() {
+        for (axes, bits) in [(2, 4), (3, 3)] {
+            let mut previous = hilbert_decode(0, axes, bits);
+            assert_eq!(previous, vec![0; axes]);
+            for index in 1..1u64 << (axes as u32 * bits) {
+                let coords = hilbert_decode(index, axes, bits);
+                assert_eq!(hilbert_encode(&coords, bits), index);
+                let steps = coords.iter().zip(previous.iter()).map(|(a, b)| a.abs_diff(*b)).sum::<u32>();
+                assert_eq!(steps, 1);
+                previous = coords;
+            }
+        }
+        assert_eq!(hilbert_decode(hilbert_encode(&[u32::MAX, 7], 32), 2, 32), vec![u32::MAX, 7]);
+        assert_eq!(hilbert_decode(hilbert_encode(&[u32::MAX], 32), 1, 32), vec![u32::MAX]);
+
+        let root = Quad::new(0.0, 0.0, 500.0, 500.0);
+        let mut items = Vec::new();
+        for x in -50..50 {
+            for y in -50..50 {
+                items.push((x * 1009 + y * 1013, Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 1.0, 1.0)));
+            }
+        }
+        let shards = hilbert_shards(&root, items.clone(), 3);
+        assert_eq!(shards.iter().map(|s| s.len()).collect::<Vec<usize>>(), vec![3334, 3333, 3333]);
+        assert!(hilbert_index(&root, &shards[0][3333].1) <= hilbert_index(&root, &shards[1][0].1));
+
+        let mut rtree = RTree::new();
+        assert_eq!(rtree.bulk_load(&root, items), 10000);
+        assert_eq!(rtree.count(), 10000);
+        assert!(rtree.validate().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 bits")]
+    fn decode_too_many_bits() {
+        hilbert_decode(5, 1, 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 32 bits")]
+    fn encode_too_many_bits() {
+        hilbert_encode(&[5], 33);
+    }
+}
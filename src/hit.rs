@@ -0,0 +1,110 @@
+use crate::{Dimension, Extents, Quad, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+use std::cmp::Reverse;
+
+///Item shape paired with a priority such as a z-index, higher priorities lying on top.
+#[derive(Debug, Clone, Copy)]
+pub struct Prioritized<T> {
+    pub shape: T,
+    pub priority: i32,
+}
+
+impl<T> Prioritized<T> {
+    pub fn new(shape: T, priority: i32) -> Self {
+        Self { shape, priority }
+    }
+}
+
+impl<D: Extents, T: Shape<D>> Shape<D> for Prioritized<T> {
+    fn bounds(&self) -> D {
+        self.shape.bounds()
+    }
+    fn overlaps_cell(&self, cell: &D) -> bool {
+        self.shape.overlaps_cell(cell)
+    }
+    fn overlaps_query(&self, area: &D) -> bool {
+        self.shape.overlaps_query(area)
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> TNode<I, S, D, T> {
+    ///Visit the objects of the nodes on the path from this node down to the leaf containing the point.
+    ///Any item under the point overlaps that leaf, so the path holds all of them.
+    fn path_objects<'a, F>(&'a self, point: &D, f: &mut F)
+    where
+        F: FnMut(&'a (I, T)),
+    {
+        self.objects.iter().for_each(&mut *f);
+        if let Some(n) = self.nodes.as_ref().and_then(|x| x.iter().find(|n| n.space.contains(point))) {
+            n.path_objects(point, f);
+        }
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> Tree<I, S, D, TNode<I, S, D, Prioritized<T>>, Prioritized<T>> {
+    ///Every item under the point, which should have zero extents, topmost first. Ties between equal priorities are broken arbitrarily.
+    pub fn hit_test(&self, point: &D) -> Vec<(I, Prioritized<T>)> {
+        let mut hits = Vec::new();
+        if self.prime.space.contains(point) {
+            self.prime.path_objects(point, &mut |(id, o)| {
+                if o.overlaps_query(point) {
+                    hits.push((id.clone(), o.clone()));
+                }
+            });
+        }
+        hits.sort_by_key(|(_, o)| Reverse(o.priority));
+        hits
+    }
+    ///The topmost item under the point, found without collecting or sorting the others.
+    pub fn hit_test_top(&self, point: &D) -> Option<(I, Prioritized<T>)> {
+        let mut top: Option<&(I, Prioritized<T>)> = None;
+        if self.prime.space.contains(point) {
+            self.prime.path_objects(point, &mut |item| {
+                if item.1.overlaps_query(point) && top.is_none_or(|t| item.1.priority > t.1.priority) {
+                    top = Some(item);
+                }
+            });
+        }
+        top.cloned()
+    }
+}
+
+///2-dimensional tree of prioritized items.
+pub type PriorityQuadTree<I> = Tree<I, 4, Quad, TNode<I, 4, Quad, Prioritized<Quad>>, Prioritized<Quad>>;
+
+impl<I: Clone> PriorityQuadTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(TNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+}
+
+#[cfg(test)]
+mod hit_tests {
+    use crate::*;
+
+    #[test]
+    fn hit_test() {
+        let mut widgets = PriorityQuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut items = Vec::new();
+        for i in 0..2000 {
+            let x = ((i * 7919) % 1000) as f32 - 499.7;
+            let y = ((i * 104729) % 1000) as f32 - 499.7;
+            let widget = Prioritized::new(Quad::new(x, y, (i % 50) as f32 + 5.0, (i % 30) as f32 + 5.0), (i * 31) % 97);
+            widgets.insert(i, widget);
+            items.push((i, widget));
+        }
+        for (x, y) in [(0.0, 0.0), (-120.5, 333.3), (250.0, -250.0), (499.0, 499.0)] {
+            let point = Quad::new(x, y, 0.0, 0.0);
+            let mut hits = widgets.hit_test(&point);
+            assert!(hits.windows(2).all(|w| w[0].1.priority >= w[1].1.priority));
+            let top = widgets.hit_test_top(&point);
+            assert_eq!(top.map(|(_, w)| w.priority), hits.first().map(|(_, w)| w.priority));
+
+            let mut expected = items.iter().filter(|(_, w)| w.shape.overlaps(&point)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            hits.sort_by_key(|(id, _)| *id);
+            assert!(hits.windows(2).all(|w| w[0].0 != w[1].0));
+            expected.sort();
+            assert_eq!(hits.into_iter().map(|(id, _)| id).collect::<Vec<i32>>(), expected);
+        }
+        assert!(widgets.hit_test(&Quad::new(600.0, 0.0, 0.0, 0.0)).is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{validate::encloses, Dimension, Node, Tree, Violation, DEFAULT_CAPACITY};
+
+///How a k-d node picks the axis to split along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitAxis {
+    ///Cycle through the axes, one per level.
+    Alternate,
+    ///Split along the axis where the item centers are spread the widest.
+    Widest,
+}
+
+///k-d tree node for point data. Leaves that overflow split at the median item center, so clustered points still produce a balanced tree.
+#[derive(Debug, Clone)]
+pub struct KdNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    bounds: Option<D>,
+    capacity: u8,
+    split_axis: SplitAxis,
+    depth: u8,
+    axis: usize,
+    ///Items whose center is below this value along the axis go to the first node, the rest to the second.
+    value: f32,
+    nodes: Option<Vec<Self>>,
+    objects: Vec<(I, D)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> KdNode<I, S, D> {
+    pub fn new(capacity: u8, split_axis: SplitAxis) -> Self {
+        Self::at_depth(capacity.max(1), split_axis, 0)
+    }
+    ///The smallest dimension enclosing everything stored below the node. None while the node is empty.
+    pub fn bounds(&self) -> Option<&D> {
+        self.bounds.as_ref()
+    }
+
+    fn at_depth(capacity: u8, split_axis: SplitAxis, depth: u8) -> Self {
+        Self {
+            bounds: None,
+            capacity,
+            split_axis,
+            depth,
+            axis: 0,
+            value: 0.0,
+            nodes: None,
+            objects: Vec::with_capacity(capacity as usize),
+        }
+    }
+
+    fn choose_axis(&self) -> usize {
+        match self.split_axis {
+            SplitAxis::Alternate => self.depth as usize % D::AXES,
+            SplitAxis::Widest => {
+                let spread = |axis: usize| {
+                    let centers = self.objects.iter().map(|(_, o)| o.center(axis));
+                    centers.clone().fold(f32::NEG_INFINITY, f32::max) - centers.fold(f32::INFINITY, f32::min)
+                };
+                (1..D::AXES).fold(0, |best, axis| if spread(axis) > spread(best) { axis } else { best })
+            }
+        }
+    }
+
+    fn side(&self, other: &D) -> usize {
+        match other.center(self.axis) < self.value {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    ///Push the k nearest items found below this node into the candidates, which are kept sorted by distance.
+    fn nearest_into(&self, point: &D, k: usize, candidates: &mut Vec<(f32, (I, D))>) {
+        let worst = match candidates.len() < k {
+            true => f32::INFINITY,
+            false => candidates[k - 1].0,
+        };
+        if self.bounds.as_ref().is_none_or(|b| b.distance(point) > worst) {
+            return;
+        }
+        match &self.nodes {
+            Some(x) => {
+                let near = self.side(point);
+                x[near].nearest_into(point, k, candidates);
+                x[1 - near].nearest_into(point, k, candidates);
+            }
+            None => {
+                for (id, o) in self.objects.iter() {
+                    let distance = o.distance(point);
+                    if candidates.len() < k || distance < candidates[k - 1].0 {
+                        let index = candidates.partition_point(|(d, _)| *d <= distance);
+                        candidates.insert(index, (distance, (id.clone(), o.clone())));
+                        candidates.truncate(k);
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_at(&self, depth: usize, violations: &mut Vec<Violation<D>>) -> usize {
+        let space = match &self.bounds {
+            Some(bounds) => bounds.clone(),
+            None => return 0,
+        };
+        for (_, item) in self.objects.iter().filter(|(_, o)| !encloses(&space, o)) {
+            violations.push(Violation::StrayItem { depth, space: space.clone(), item: item.clone() });
+        }
+        let mut found = self.objects.len();
+        if let Some(x) = &self.nodes {
+            if !self.objects.is_empty() {
+                violations.push(Violation::InternalObjects { depth, space: space.clone(), objects: self.objects.len() });
+            }
+            if x.len() != 2 {
+                violations.push(Violation::Tiling { depth, space: space.clone() });
+            }
+            for (side, n) in x.iter().enumerate() {
+                if n.bounds.as_ref().is_some_and(|b| !encloses(&space, b)) {
+                    violations.push(Violation::Bounds { depth: depth + 1, space: space.clone() });
+                }
+                for (_, item) in n.objects.iter().filter(|(_, o)| self.side(o) != side) {
+                    violations.push(Violation::StrayItem { depth: depth + 1, space: space.clone(), item: item.clone() });
+                }
+                found += n.validate_at(depth + 1, violations);
+            }
+        }
+        found
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for KdNode<I, S, D> {
+    fn insert(&mut self, id: I, other: D) -> bool {
+        self.bounds = Some(match &self.bounds {
+            Some(bounds) => bounds.union(&other),
+            None => other.clone(),
+        });
+        let side = self.side(&other);
+        match &mut self.nodes {
+            Some(x) => {
+                x[side].insert(id, other);
+            }
+            None => {
+                self.objects.push((id, other));
+                if self.objects.len() > self.capacity as usize {
+                    self.subdivide();
+                }
+            }
+        }
+        true
+    }
+    ///Split a leaf at the median item center. Leaves whose items all share one center along the axis are left alone.
+    fn subdivide(&mut self) {
+        if self.nodes.is_some() || self.objects.len() < 2 {
+            return;
+        }
+        let axis = self.choose_axis();
+        let median = self.objects.len() / 2;
+        self.objects.select_nth_unstable_by(median, |(_, a), (_, b)| a.center(axis).total_cmp(&b.center(axis)));
+        let value = self.objects[median].1.center(axis);
+        if self.objects.iter().all(|(_, o)| o.center(axis) >= value) {
+            return;
+        }
+        self.axis = axis;
+        self.value = value;
+        let mut nodes = vec![
+            Self::at_depth(self.capacity, self.split_axis, self.depth.saturating_add(1)),
+            Self::at_depth(self.capacity, self.split_axis, self.depth.saturating_add(1)),
+        ];
+        for (id, o) in std::mem::take(&mut self.objects) {
+            nodes[self.side(&o)].insert(id, o);
+        }
+        self.nodes = Some(nodes);
+    }
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        if self.bounds.as_ref().is_some_and(|b| b.overlaps(area)) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| o.overlaps(area)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search(area, buffer);
+                }
+            }
+        }
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        if self.bounds.as_ref().is_some_and(overlaps) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| overlaps(o)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search_with(overlaps, buffer);
+                }
+            }
+        }
+    }
+    fn clear(&mut self) {
+        *self = Self::at_depth(self.capacity, self.split_axis, self.depth);
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, violations)
+    }
+}
+
+///k-d tree representation over any dimension.
+pub type KdTree<I, const S: usize, D> = Tree<I, S, D, KdNode<I, S, D>>;
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> KdTree<I, S, D> {
+    pub fn new() -> Self {
+        Tree::new_tree(KdNode::new(DEFAULT_CAPACITY, SplitAxis::Widest))
+    }
+    ///Find the k items closest to the point, nearest first.
+    pub fn nearest(&self, point: &D, k: usize) -> Vec<(I, D)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut candidates = Vec::with_capacity(k + 1);
+        self.prime.nearest_into(point, k, &mut candidates);
+        candidates.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Default for KdTree<I, S, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod kd_tests {
+    use crate::*;
+
+    #[test]
+    fn kdtree() {
+        for split_axis in [SplitAxis::Alternate, SplitAxis::Widest] {
+            let mut kdtree = KdTree::new_tree(KdNode::new(DEFAULT_CAPACITY, split_axis));
+            let mut points = Vec::new();
+            for i in 0..5000 {
+                let cluster = (i % 5) as f32 * 200.0 - 400.0;
+                let x = cluster + ((i * 7919) % 1000) as f32 / 100.0;
+                let y = cluster + ((i * 104729) % 1000) as f32 / 100.0;
+                let z = ((i * 31) % 100) as f32;
+                kdtree.insert(i, Oct::new(x, y, z, 0.0, 0.0, 0.0));
+                points.push((i, Oct::new(x, y, z, 0.0, 0.0, 0.0)));
+            }
+            assert_eq!(kdtree.count(), 5000);
+            assert!(kdtree.validate().is_ok());
+
+            let area = Oct::new(-400.0, -400.0, 50.0, 5.0, 5.0, 20.0);
+            let mut result = kdtree.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            let mut expected = points.iter().filter(|(_, p)| p.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            result.sort();
+            expected.sort();
+            assert_eq!(result, expected);
+
+            let point = Oct::new(3.0, -1.0, 20.0, 0.0, 0.0, 0.0);
+            let nearest = kdtree.nearest(&point, 10);
+            points.sort_by(|(_, a), (_, b)| a.distance(&point).total_cmp(&b.distance(&point)));
+            let distances = nearest.iter().map(|(_, p)| p.distance(&point)).collect::<Vec<f32>>();
+            let expected = points[..10].iter().map(|(_, p)| p.distance(&point)).collect::<Vec<f32>>();
+            assert_eq!(distances, expected);
+        }
+    }
+}
@@ -1,11 +1,42 @@
+mod batch;
+mod bvh;
+mod circle;
+mod count;
+mod export;
+mod frozen;
+mod frustum;
+mod grid;
+mod hilbert;
+mod hit;
+mod kd;
+#[allow(clippy::neg_cmp_op_on_partial_ord)]
 mod line;
+mod loose;
+mod morton;
+mod polygon;
 mod quad;
+mod query;
+mod obb;
 mod oct;
+mod point;
+mod rtree;
+mod segment;
+mod sphere;
+mod stab;
+mod stats;
+mod svg;
+#[allow(clippy::too_many_arguments)]
 mod tess;
+#[cfg(test)]
+mod testing;
+#[allow(clippy::box_collection, clippy::assign_op_pattern, clippy::needless_return, clippy::useless_conversion, clippy::absurd_extreme_comparisons)]
 mod tnode;
+#[allow(clippy::module_inception)]
 mod tree;
+mod validate;
 
-pub use crate::{line::*, quad::*, oct::*, tess::*, tnode::*, tree::*};
+pub use crate::{bvh::*, circle::*, frozen::*, frustum::*, grid::*, hilbert::*, hit::*, kd::*, line::*, loose::*, morton::*, quad::*, query::*, obb::*, oct::*, point::*, polygon::*, rtree::*, segment::*, sphere::*, stats::*, svg::*, tess::*, tnode::*, tree::*, validate::*};
 
 pub const DEFAULT_CAPACITY: u8 = 17;
-pub const DEFAULT_MAX_DEPTH: u8 = 8;
\ No newline at end of file
+pub const DEFAULT_MAX_DEPTH: u8 = 8;
+pub const DEFAULT_LOOSENESS: f32 = 2.0;
\ No newline at end of file
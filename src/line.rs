@@ -1,4 +1,4 @@
-use crate::{Dimension, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+use crate::{Dimension, Extents, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
 
 ///1-dimensional line.
 #[derive(Debug, Clone, Copy)]
@@ -17,9 +17,20 @@ impl Dimension<2> for Line {
     fn overlaps(&self, area: &Self) -> bool {
         !( (self.x - area.x).abs() > (self.half_x + area.half_x))
     }
+    fn contains(&self, other: &Self) -> bool {
+        (self.x - other.x).abs() + other.half_x <= self.half_x
+    }
+    fn union(&self, other: &Self) -> Self {
+        let min_x = (self.x - self.half_x).min(other.x - other.half_x);
+        let max_x = (self.x + self.half_x).max(other.x + other.half_x);
+        Self::new(
+            (min_x + max_x) / 2.0,
+            (max_x - min_x) / 2.0,
+        )
+    }
     fn contains_center(&self, point: &Self) -> bool {
         !( point.x >= self.x + self.half_x
-        || point.x <= self.x - self.half_x)
+        || point.x < self.x - self.half_x)
     }
     fn subdivisions(&self) -> [Self; 2] {
         let half_x = self.half_x / 2.0;
@@ -38,6 +49,28 @@ impl Dimension<2> for Line {
     }
 }
 
+impl Extents for Line {
+    const AXES: usize = 1;
+    fn center(&self, axis: usize) -> f32 {
+        [self.x][axis]
+    }
+    fn half(&self, axis: usize) -> f32 {
+        [self.half_x][axis]
+    }
+    fn from_extents(center: &[f32], half: &[f32]) -> Self {
+        Self::new(center[0], half[0])
+    }
+}
+
+impl Shape<Line> for Line {
+    fn bounds(&self) -> Line {
+        *self
+    }
+    fn overlaps_cell(&self, cell: &Line) -> bool {
+        self.overlaps(cell)
+    }
+}
+
 ///1-dimensional tree representation.
 pub type LineTree<I> = Tree<I, 2, Line, TNode<I, 2, Line>>;
 
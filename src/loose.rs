@@ -0,0 +1,195 @@
+use crate::{validate::tiles, Dimension, Extents, Node, Oct, Quad, Tree, Violation, DEFAULT_CAPACITY, DEFAULT_LOOSENESS, DEFAULT_MAX_DEPTH};
+
+///Node whose bounds are enlarged by a looseness factor, so that every item is stored exactly once, in the deepest node that fully holds it.
+#[derive(Debug, Clone)]
+pub struct LooseNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    space: D,
+    loose: D,
+    looseness: f32,
+    capacity: u8,
+    depth_limit: u8,
+    nodes: Option<Vec<Self>>,
+    objects: Vec<(I, D)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> LooseNode<I, S, D> {
+    pub fn new(rect: D, looseness: f32, capacity: u8, depth: u8) -> Self {
+        Self {
+            loose: loosen(&rect, looseness),
+            space: rect,
+            looseness,
+            capacity,
+            depth_limit: depth,
+            nodes: None,
+            objects: Vec::new(),
+        }
+    }
+    ///The node's space enlarged by the looseness factor. Every item stored below the root lies entirely inside it.
+    pub fn loose(&self) -> &D {
+        &self.loose
+    }
+
+    fn place(&mut self, id: I, other: D) {
+        if let Some(nodes) = &mut self.nodes {
+            let child = nearest(nodes, &other);
+            if nodes[child].loose.contains(&other) {
+                nodes[child].place(id, other);
+            } else {
+                self.objects.push((id, other));
+            }
+            return;
+        }
+        self.objects.push((id, other));
+        if self.objects.len() > self.capacity as usize {
+            self.subdivide();
+        }
+    }
+
+    fn validate_at(&self, depth: usize, violations: &mut Vec<Violation<D>>) -> usize {
+        for (_, item) in self.objects.iter() {
+            let stored = match depth {
+                0 => self.space.overlaps(item),
+                _ => self.loose.contains(item),
+            };
+            if !stored {
+                violations.push(Violation::StrayItem { depth, space: self.loose.clone(), item: item.clone() });
+            }
+        }
+        let mut found = self.objects.len();
+        if let Some(nodes) = &self.nodes {
+            if self.depth_limit == 0 {
+                violations.push(Violation::DepthLimit { depth, space: self.space.clone() });
+            }
+            if !tiles(&self.space, nodes.iter().map(|n| &n.space)) {
+                violations.push(Violation::Tiling { depth, space: self.space.clone() });
+            }
+            for n in nodes.iter() {
+                if self.depth_limit.checked_sub(1) != Some(n.depth_limit) {
+                    violations.push(Violation::DepthLimit { depth: depth + 1, space: n.space.clone() });
+                }
+                found += n.validate_at(depth + 1, violations);
+            }
+        }
+        found
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for LooseNode<I, S, D> {
+    fn insert(&mut self, id: I, other: D) -> bool {
+        if !self.space.overlaps(&other) {
+            return false;
+        }
+        self.place(id, other);
+        true
+    }
+    fn subdivide(&mut self) {
+        if self.depth_limit == 0 {
+            return;
+        }
+        self.nodes = Some(
+            self.space
+                .subdivisions()
+                .iter()
+                .map(|d| Self::new(d.clone(), self.looseness, self.capacity, self.depth_limit - 1))
+                .collect::<Vec<Self>>(),
+        );
+        for (id, o) in std::mem::take(&mut self.objects) {
+            self.place(id, o);
+        }
+    }
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        if self.loose.overlaps(area) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| area.overlaps(o)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search(area, buffer);
+                }
+            }
+        }
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        if overlaps(&self.loose) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| overlaps(o)).cloned());
+            if let Some(x) = &self.nodes {
+                for n in x.iter() {
+                    n.search_with(overlaps, buffer);
+                }
+            }
+        }
+    }
+    fn clear(&mut self) {
+        self.objects.clear();
+        if let Some(x) = &mut self.nodes {
+            for i in x.iter_mut() {
+                i.clear();
+            }
+        }
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, violations)
+    }
+}
+
+fn loosen<D: Extents>(space: &D, looseness: f32) -> D {
+    let center = (0..D::AXES).map(|axis| space.center(axis)).collect::<Vec<f32>>();
+    let half = (0..D::AXES).map(|axis| space.half(axis) * looseness).collect::<Vec<f32>>();
+    D::from_extents(&center, &half)
+}
+
+///Index of the node whose space is closest to the center of the other dimension, which is the node containing it when there is one.
+fn nearest<I: Clone, const S: usize, D: Clone + Dimension<S>>(nodes: &[LooseNode<I, S, D>], other: &D) -> usize {
+    let distance = |n: &LooseNode<I, S, D>| {
+        (0..D::AXES)
+            .map(|axis| (other.center(axis) - n.space.center(axis)).abs() / n.space.half(axis))
+            .fold(0.0, f32::max)
+    };
+    (1..nodes.len()).fold(0, |best, i| if distance(&nodes[i]) < distance(&nodes[best]) { i } else { best })
+}
+
+///2-dimensional loose tree representation.
+pub type LooseQuadTree<I> = Tree<I, 4, Quad, LooseNode<I, 4, Quad>>;
+
+impl<I: Clone> LooseQuadTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(LooseNode::new(translation, DEFAULT_LOOSENESS, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+}
+
+///3-dimensional loose tree representation.
+pub type LooseOctTree<I> = Tree<I, 8, Oct, LooseNode<I, 8, Oct>>;
+
+impl<I: Clone> LooseOctTree<I> {
+    pub fn new(translation: Oct) -> Self {
+        Tree::new_tree(LooseNode::new(translation, DEFAULT_LOOSENESS, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+}
+
+#[cfg(test)]
+mod loose_tests {
+    use crate::*;
+
+    #[test]
+    fn loose_quadtree() {
+        let mut quadtree = LooseQuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut items = Vec::new();
+        for i in 0..1000 {
+            let x = ((i * 7919) % 1000) as f32 - 500.0;
+            let y = ((i * 104729) % 1000) as f32 - 500.0;
+            let item = Quad::new(x, y, (i % 60) as f32 + 0.5, (i % 45) as f32 + 0.5);
+            quadtree.insert(i, item);
+            items.push((i, item));
+        }
+        assert_eq!(quadtree.count(), 1000);
+        assert!(quadtree.validate().is_ok());
+
+        for area in [Quad::new(0.0, 0.0, 0.5, 0.5), Quad::new(-120.0, 40.0, 60.0, 30.0)] {
+            let mut result = quadtree.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            let expected = items.iter().filter(|(_, item)| item.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            result.sort();
+            assert_eq!(result, expected);
+        }
+    }
+}
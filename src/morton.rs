@@ -0,0 +1,310 @@
+use crate::{Dimension, Extents, Node, Oct, Quad, Tree, Violation};
+
+///How many levels range queries descend into before settling for a partly covered cell and filtering its items.
+const DECOMPOSE_DEPTH: u32 = 8;
+///Fewest items kept unsorted at the end of the array before they are merged in, which also grows with the array so merges stay rare.
+const UNSORTED: usize = 64;
+
+///Interleave the bits of the coordinates into a Morton (Z-order) code, the lowest bit of the first coordinate first.
+///Coordinates contribute `64 / coords.len()` bits each, so the code of up to 2 coordinates fits without loss. No coordinates encode to 0.
+pub fn morton_encode(coords: &[u32]) -> u64 {
+    if coords.is_empty() {
+        return 0;
+    }
+    morton_encode_bits(coords, (64 / coords.len() as u32).min(32))
+}
+
+///Split a Morton code back into its coordinates.
+pub fn morton_decode(code: u64, axes: usize) -> Vec<u32> {
+    if axes == 0 {
+        return Vec::new();
+    }
+    let bits = (64 / axes).min(32);
+    let mut coords = vec![0; axes];
+    for bit in 0..bits {
+        for (axis, coord) in coords.iter_mut().enumerate() {
+            *coord |= (((code >> (bit * axes + axis)) & 1) as u32) << bit;
+        }
+    }
+    coords
+}
+
+///Number of bits per axis used to place dimensions with this many axes on a grid, keeping the interleaved code below 64 bits.
+pub(crate) fn grid_bits(axes: usize) -> u32 {
+    (63 / axes as u32).min(32)
+}
+
+///Position of the dimension's center on a grid of 2^bits cells per axis laid over the root. Centers outside the root are clamped to its edge.
+pub(crate) fn quantize<D: Extents>(root: &D, d: &D, bits: u32) -> Vec<u32> {
+    let cells = (1u64 << bits) as f64;
+    (0..D::AXES)
+        .map(|axis| {
+            let offset = (d.center(axis) - root.min(axis)) as f64 / (root.half(axis) * 2.0) as f64;
+            (offset * cells).floor().clamp(0.0, cells - 1.0) as u32
+        })
+        .collect()
+}
+
+///Linear quadtree/octree storing items in a flat array sorted by the Morton code of their center. New items are appended and
+///merged into the sorted part in batches, and `from_items` sorts a whole set at once.
+#[derive(Debug, Clone)]
+pub struct LinearNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    root: D,
+    bits: u32,
+    ///Largest half size along each axis of any stored item, which is how far past a cell an item centered in it can reach.
+    reach: Vec<f32>,
+    codes: Vec<u64>,
+    objects: Vec<(I, D)>,
+    ///Length of the sorted part of the array. Items after it were inserted since the last merge.
+    sorted: usize,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> LinearNode<I, S, D> {
+    pub fn new(root: D) -> Self {
+        Self {
+            bits: grid_bits(D::AXES),
+            reach: vec![0.0; D::AXES],
+            root,
+            codes: Vec::new(),
+            objects: Vec::new(),
+            sorted: 0,
+        }
+    }
+    ///Build the array from many items, sorting them once. Items whose center lies outside the root are left out.
+    pub fn from_items(root: D, items: impl IntoIterator<Item = (I, D)>) -> Self {
+        let mut node = Self::new(root);
+        for (id, item) in items {
+            node.push(id, item);
+        }
+        node.merge();
+        node
+    }
+    ///Locational code of a dimension: the Morton code of its center's cell on the finest grid laid over the root.
+    pub fn code(&self, d: &D) -> u64 {
+        morton_encode_bits(&quantize(&self.root, d, self.bits), self.bits)
+    }
+    ///Every stored item along with its locational code, in code order once `sort` has merged the recently inserted ones.
+    pub fn items(&self) -> impl Iterator<Item = (u64, &(I, D))> {
+        self.codes.iter().copied().zip(self.objects.iter())
+    }
+    ///Append the item unsorted, returning false when its center lies outside the root.
+    fn push(&mut self, id: I, other: D) -> bool {
+        if !(0..D::AXES).all(|axis| self.root.min(axis) <= other.center(axis) && other.center(axis) <= self.root.max(axis)) {
+            return false;
+        }
+        for axis in 0..D::AXES {
+            self.reach[axis] = self.reach[axis].max(other.half(axis));
+        }
+        self.codes.push(self.code(&other));
+        self.objects.push((id, other));
+        true
+    }
+
+    fn merge(&mut self) {
+        if self.sorted == self.codes.len() {
+            return;
+        }
+        let mut unsorted = self.codes.split_off(self.sorted).into_iter().zip(self.objects.split_off(self.sorted)).collect::<Vec<(u64, (I, D))>>();
+        unsorted.sort_by_key(|(code, _)| *code);
+        let sorted = std::mem::take(&mut self.codes).into_iter().zip(std::mem::take(&mut self.objects));
+        let (mut sorted, mut unsorted) = (sorted.peekable(), unsorted.into_iter().peekable());
+        while let Some(next) = match (sorted.peek(), unsorted.peek()) {
+            (Some(a), Some(b)) if b.0 < a.0 => unsorted.next(),
+            (Some(_), _) => sorted.next(),
+            (None, _) => unsorted.next(),
+        } {
+            self.codes.push(next.0);
+            self.objects.push(next.1);
+        }
+        self.sorted = self.codes.len();
+    }
+
+    ///Collect the code ranges of every cell the filter accepts, merging neighbors. Cells are visited in code order.
+    fn ranges<CF>(&self, accepts: &CF) -> Vec<(u64, u64)>
+    where
+        CF: Fn(&D) -> bool,
+    {
+        let mut ranges = Vec::new();
+        self.decompose(&vec![0; D::AXES], 0, accepts, &mut ranges);
+        ranges
+    }
+
+    fn decompose<CF>(&self, origin: &[u32], level: u32, accepts: &CF, ranges: &mut Vec<(u64, u64)>)
+    where
+        CF: Fn(&D) -> bool,
+    {
+        let shift = self.bits - level;
+        let cells = (1u64 << level) as f32;
+        let center = (0..D::AXES)
+            .map(|axis| self.root.min(axis) + (((origin[axis] >> shift) as f32 + 0.5) / cells) * self.root.half(axis) * 2.0)
+            .collect::<Vec<f32>>();
+        let half = (0..D::AXES).map(|axis| self.root.half(axis) / cells + self.reach[axis]).collect::<Vec<f32>>();
+        if level > 0 && !accepts(&D::from_extents(&center, &half)) {
+            return;
+        }
+        if level == DECOMPOSE_DEPTH.min(self.bits) {
+            let start = morton_encode_bits(origin, self.bits);
+            let end = start + ((1u64 << (shift as usize * D::AXES)) - 1);
+            match ranges.last_mut() {
+                Some(last) if last.1.checked_add(1) == Some(start) => last.1 = end,
+                _ => ranges.push((start, end)),
+            }
+            return;
+        }
+        for child in 0..1usize << D::AXES {
+            let origin = (0..D::AXES)
+                .map(|axis| origin[axis] | ((((child >> axis) & 1) as u32) << (shift - 1)))
+                .collect::<Vec<u32>>();
+            self.decompose(&origin, level + 1, accepts, ranges);
+        }
+    }
+
+    fn search_ranges<OF>(&self, ranges: &[(u64, u64)], overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        let codes = &self.codes[..self.sorted];
+        for (start, end) in ranges {
+            let first = codes.partition_point(|c| c < start);
+            let last = codes.partition_point(|c| c <= end);
+            buffer.extend(self.objects[first..last].iter().filter(|(_, o)| overlaps(o)).cloned());
+        }
+        buffer.extend(self.objects[self.sorted..].iter().filter(|(_, o)| overlaps(o)).cloned());
+    }
+}
+
+///Interleave coordinates that use the given number of bits each.
+fn morton_encode_bits(coords: &[u32], bits: u32) -> u64 {
+    let axes = coords.len();
+    let mut code = 0;
+    for bit in 0..bits as usize {
+        for (axis, coord) in coords.iter().enumerate() {
+            code |= ((*coord as u64 >> bit) & 1) << (bit * axes + axis);
+        }
+    }
+    code
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for LinearNode<I, S, D> {
+    fn insert(&mut self, id: I, other: D) -> bool {
+        if !self.push(id, other) {
+            return false;
+        }
+        if self.codes.len() - self.sorted > UNSORTED.max(self.sorted / 8) {
+            self.merge();
+        }
+        true
+    }
+    ///Items are kept in one sorted array, so there is nothing to subdivide.
+    fn subdivide(&mut self) {}
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        let ranges = self.ranges(&|cell: &D| cell.overlaps(area));
+        self.search_ranges(&ranges, &|o: &D| o.overlaps(area), buffer);
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        let ranges = self.ranges(overlaps);
+        self.search_ranges(&ranges, overlaps, buffer);
+    }
+    fn clear(&mut self) {
+        self.reach = vec![0.0; D::AXES];
+        self.codes.clear();
+        self.objects.clear();
+        self.sorted = 0;
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        for (i, (code, (_, item))) in self.items().enumerate() {
+            let sorted = i == 0 || i >= self.sorted || self.codes[i - 1] <= code;
+            let reached = (0..D::AXES).all(|axis| item.half(axis) <= self.reach[axis]);
+            if !sorted || !reached || self.code(item) != code {
+                violations.push(Violation::StrayItem { depth: 0, space: self.root.clone(), item: item.clone() });
+            }
+        }
+        self.objects.len()
+    }
+}
+
+///2-dimensional linear tree representation.
+pub type LinearQuadTree<I> = Tree<I, 4, Quad, LinearNode<I, 4, Quad>>;
+
+impl<I: Clone> LinearQuadTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(LinearNode::new(translation))
+    }
+}
+
+///3-dimensional linear tree representation.
+pub type LinearOctTree<I> = Tree<I, 8, Oct, LinearNode<I, 8, Oct>>;
+
+impl<I: Clone> LinearOctTree<I> {
+    pub fn new(translation: Oct) -> Self {
+        Tree::new_tree(LinearNode::new(translation))
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, LinearNode<I, S, D>> {
+    ///Build the tree from many items, sorting them once instead of merging them in batches. Items centered outside the root are left out.
+    pub fn from_items(root: D, items: impl IntoIterator<Item = (I, D)>) -> Self {
+        let mut tree = Tree::new_tree(LinearNode::from_items(root, items));
+        tree.count = tree.prime.objects.len();
+        tree
+    }
+    ///Merge the recently inserted items into the sorted array, so `items` lists everything in code order.
+    pub fn sort(&mut self) {
+        self.prime.merge();
+    }
+}
+
+#[cfg(test)]
+mod morton_tests {
+    use crate::*;
+
+    #[test]
+    fn morton() {
+        assert_eq!(morton_encode(&[0b11, 0b00]), 0b0101);
+        assert_eq!(morton_encode(&[0b10, 0b01, 0b11]), 0b101_110);
+        assert_eq!(morton_encode(&[]), 0);
+        assert!(morton_decode(0, 0).is_empty());
+        for coords in [vec![123456, 654321], vec![1023, 0, 511], vec![u32::MAX, 7]] {
+            assert_eq!(morton_decode(morton_encode(&coords), coords.len()), coords);
+        }
+
+        let mut quadtree = LinearQuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut items = Vec::new();
+        for i in 0..2000 {
+            let point = Quad::new(((i * 7919) % 1000) as f32 - 499.5, ((i * 104729) % 1000) as f32 - 499.5, 0.0, 0.0);
+            quadtree.insert(i, point);
+            items.push((i, point));
+        }
+        assert!(!quadtree.insert(2000, Quad::new(700.0, 0.0, 1.0, 1.0)));
+        assert!(quadtree.insert(2001, Quad::new(500.0, 500.0, 0.0, 0.0)));
+        items.push((2001, Quad::new(500.0, 500.0, 0.0, 0.0)));
+        assert_eq!(quadtree.count(), 2001);
+        assert!(quadtree.validate().is_ok());
+        assert!(!quadtree.prime().items().map(|(code, _)| code).is_sorted());
+        let loaded = LinearQuadTree::from_items(Quad::new(0.0, 0.0, 500.0, 500.0), items.iter().copied().chain([(2000, Quad::new(700.0, 0.0, 1.0, 1.0))]));
+        assert_eq!(loaded.count(), 2001);
+        assert!(loaded.validate().is_ok());
+        assert!(loaded.prime().items().map(|(code, _)| code).is_sorted());
+        let sw = quadtree.prime().code(&Quad::new(-499.0, -499.0, 0.0, 0.0));
+        let ne = quadtree.prime().code(&Quad::new(499.0, 499.0, 0.0, 0.0));
+        assert!(sw < ne);
+
+        let areas = [Quad::new(0.0, 0.0, 50.0, 50.0), Quad::new(-480.0, 120.0, 3.0, 200.0), Quad::new(495.0, 495.0, 5.0, 5.0), Quad::new(700.0, 0.0, 5.0, 5.0)];
+        for tree in [&loaded, &quadtree] {
+            for area in areas {
+                let mut result = tree.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+                let expected = items.iter().filter(|(_, item)| item.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+                result.sort();
+                assert_eq!(result, expected);
+            }
+        }
+        assert!(!quadtree.search(&areas[0]).is_empty());
+        quadtree.sort();
+        assert!(quadtree.validate().is_ok());
+        assert!(quadtree.prime().items().map(|(code, _)| code).is_sorted());
+        assert!(quadtree.search(&areas[3]).is_empty());
+    }
+}
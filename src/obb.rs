@@ -0,0 +1,269 @@
+use crate::{Oct, OctTree, Quad, QuadTree, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+
+///Box in any number of axes, given by its center, half sizes and orthonormal axes.
+struct Oriented<const N: usize> {
+    center: [f32; N],
+    half: [f32; N],
+    axes: [[f32; N]; N],
+}
+
+impl<const N: usize> Oriented<N> {
+    fn aligned(center: [f32; N], half: [f32; N]) -> Self {
+        let mut axes = [[0.0; N]; N];
+        for (i, axis) in axes.iter_mut().enumerate() {
+            axis[i] = 1.0;
+        }
+        Self { center, half, axes }
+    }
+
+    ///Half the length of the box's projection onto the axis.
+    fn radius(&self, axis: &[f32; N]) -> f32 {
+        (0..N).map(|i| self.half[i] * dot(&self.axes[i], axis).abs()).sum()
+    }
+
+    ///Separating axis test. Candidates are the axes of both boxes, plus their pairwise cross products in 3 dimensions.
+    fn overlaps(&self, other: &Self) -> bool {
+        let mut candidates = self.axes.iter().chain(other.axes.iter()).copied().collect::<Vec<[f32; N]>>();
+        if N == 3 {
+            for a in self.axes.iter() {
+                for b in other.axes.iter() {
+                    let mut cross = [0.0; N];
+                    for (i, c) in cross.iter_mut().enumerate() {
+                        *c = a[(i + 1) % 3] * b[(i + 2) % 3] - a[(i + 2) % 3] * b[(i + 1) % 3];
+                    }
+                    if dot(&cross, &cross) > 1e-10 {
+                        candidates.push(cross);
+                    }
+                }
+            }
+        }
+        let mut offset = [0.0; N];
+        for (i, o) in offset.iter_mut().enumerate() {
+            *o = other.center[i] - self.center[i];
+        }
+        candidates.iter().all(|axis| dot(&offset, axis).abs() <= self.radius(axis) + other.radius(axis))
+    }
+
+    ///Half sizes of the axis aligned box enclosing this one.
+    fn reach(&self) -> [f32; N] {
+        let mut reach = [0.0; N];
+        for (j, r) in reach.iter_mut().enumerate() {
+            *r = (0..N).map(|i| self.half[i] * self.axes[i][j].abs()).sum();
+        }
+        reach
+    }
+}
+
+fn dot<const N: usize>(a: &[f32; N], b: &[f32; N]) -> f32 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+///2-dimensional box rotated counterclockwise by an angle in radians.
+#[derive(Debug, Clone, Copy)]
+pub struct Obb2 {
+    pub x: f32,
+    pub y: f32,
+    pub half_x: f32,
+    pub half_y: f32,
+    pub angle: f32,
+}
+
+impl Obb2 {
+    pub fn new(x: f32, y: f32, half_x: f32, half_y: f32, angle: f32) -> Self {
+        Self { x, y, half_x, half_y, angle }
+    }
+    pub fn overlaps_obb(&self, other: &Obb2) -> bool {
+        self.oriented().overlaps(&other.oriented())
+    }
+
+    fn oriented(&self) -> Oriented<2> {
+        let (sin, cos) = self.angle.sin_cos();
+        Oriented { center: [self.x, self.y], half: [self.half_x, self.half_y], axes: [[cos, sin], [-sin, cos]] }
+    }
+}
+
+impl Shape<Quad> for Obb2 {
+    fn bounds(&self) -> Quad {
+        let [reach_x, reach_y] = self.oriented().reach();
+        Quad::new(self.x, self.y, reach_x, reach_y)
+    }
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        self.oriented().overlaps(&Oriented::aligned([cell.x, cell.y], [cell.half_x, cell.half_y]))
+    }
+}
+
+///3-dimensional box whose local x, y and z axes point along the rows of `axes`, which should be orthonormal.
+#[derive(Debug, Clone, Copy)]
+pub struct Obb3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub half_x: f32,
+    pub half_y: f32,
+    pub half_z: f32,
+    pub axes: [[f32; 3]; 3],
+}
+
+impl Obb3 {
+    pub fn new(x: f32, y: f32, z: f32, half_x: f32, half_y: f32, half_z: f32, axes: [[f32; 3]; 3]) -> Self {
+        Self { x, y, z, half_x, half_y, half_z, axes }
+    }
+    pub fn overlaps_obb(&self, other: &Obb3) -> bool {
+        self.oriented().overlaps(&other.oriented())
+    }
+
+    fn oriented(&self) -> Oriented<3> {
+        Oriented { center: [self.x, self.y, self.z], half: [self.half_x, self.half_y, self.half_z], axes: self.axes }
+    }
+}
+
+impl Shape<Oct> for Obb3 {
+    fn bounds(&self) -> Oct {
+        let [reach_x, reach_y, reach_z] = self.oriented().reach();
+        Oct::new(self.x, self.y, self.z, reach_x, reach_y, reach_z)
+    }
+    fn overlaps_cell(&self, cell: &Oct) -> bool {
+        self.oriented().overlaps(&Oriented::aligned([cell.x, cell.y, cell.z], [cell.half_x, cell.half_y, cell.half_z]))
+    }
+}
+
+///2-dimensional tree of oriented boxes.
+pub type ObbQuadTree<I> = Tree<I, 4, Quad, TNode<I, 4, Quad, Obb2>, Obb2>;
+
+impl<I: Clone> ObbQuadTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(TNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+    ///Find the oriented boxes overlapping the oriented box.
+    pub fn search_obb(&self, obb: &Obb2) -> Vec<(I, Obb2)> {
+        self.search(&obb.bounds()).into_iter().filter(|(_, o)| o.overlaps_obb(obb)).collect()
+    }
+}
+
+impl<I: Clone> QuadTree<I> {
+    ///Find the items overlapping the oriented box.
+    pub fn search_obb(&self, obb: &Obb2) -> Vec<(I, Quad)> {
+        self.search(&obb.bounds()).into_iter().filter(|(_, q)| obb.overlaps_query(q)).collect()
+    }
+}
+
+///3-dimensional tree of oriented boxes.
+pub type ObbOctTree<I> = Tree<I, 8, Oct, TNode<I, 8, Oct, Obb3>, Obb3>;
+
+impl<I: Clone> ObbOctTree<I> {
+    pub fn new(translation: Oct) -> Self {
+        Tree::new_tree(TNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+    ///Find the oriented boxes overlapping the oriented box.
+    pub fn search_obb(&self, obb: &Obb3) -> Vec<(I, Obb3)> {
+        self.search(&obb.bounds()).into_iter().filter(|(_, o)| o.overlaps_obb(obb)).collect()
+    }
+}
+
+impl<I: Clone> OctTree<I> {
+    ///Find the items overlapping the oriented box.
+    pub fn search_obb(&self, obb: &Obb3) -> Vec<(I, Oct)> {
+        self.search(&obb.bounds()).into_iter().filter(|(_, o)| obb.overlaps_query(o)).collect()
+    }
+}
+
+#[cfg(test)]
+mod obb_tests {
+    use crate::{testing::ids, *};
+    use std::f32::consts::FRAC_PI_4;
+
+    ///Cell edges of the first few subdivisions of a root spanning -500 to 500.
+    const EDGES: [f32; 7] = [-250.0, -125.0, -62.5, 0.0, 62.5, 125.0, 250.0];
+
+    ///Rows of the rotation by a about z followed by b about x.
+    fn rotation(a: f32, b: f32) -> [[f32; 3]; 3] {
+        let ((sa, ca), (sb, cb)) = (a.sin_cos(), b.sin_cos());
+        [[ca, -sa * cb, sa * sb], [sa, ca * cb, -ca * sb], [0.0, sb, cb]]
+    }
+
+    #[test]
+    fn obbs() {
+        let diamond = Obb2::new(0.0, 0.0, 10.0, 10.0, FRAC_PI_4);
+        let corner = Quad::new(12.0, 12.0, 2.0, 2.0);
+        assert!(diamond.bounds().overlaps(&corner));
+        assert!(!diamond.overlaps_cell(&corner));
+        assert!(diamond.overlaps_cell(&Quad::new(14.0, 0.0, 1.0, 1.0)));
+        assert!(!diamond.overlaps_obb(&Obb2::new(16.0, 16.0, 10.0, 1.0, -FRAC_PI_4)));
+        assert!(diamond.overlaps_obb(&Obb2::new(12.0, 0.0, 10.0, 1.0, 0.3)));
+
+        let mut obbtree = ObbQuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let (mut obbs, mut quads) = (Vec::new(), Vec::new());
+        for (i, (x, y)) in EDGES.iter().flat_map(|edge| (0..40).flat_map(move |step| [(*edge, step as f32 * 25.0 - 487.5), (step as f32 * 25.0 - 487.5, *edge)])).enumerate() {
+            let (obb, quad) = (Obb2::new(x, y, 12.0, 2.0, i as f32 * 0.37), Quad::new(x, y, 6.0, 3.0));
+            obbtree.insert(i, obb);
+            quadtree.insert(i, quad);
+            obbs.push((i, obb));
+            quads.push((i, quad));
+        }
+        assert!(obbtree.validate().is_ok());
+        assert!(quadtree.validate().is_ok());
+
+        let mut loose = 0;
+        for query in [Obb2::new(0.0, 0.0, 80.0, 6.0, 0.6), Obb2::new(125.0, -250.0, 40.0, 40.0, 1.2), Obb2::new(-300.0, 200.0, 150.0, 3.0, -0.4)] {
+            let result = obbtree.search_obb(&query);
+            assert!(result.iter().all(|(_, o)| o.overlaps_obb(&query)));
+            let expected = obbs.iter().filter(|(_, o)| o.overlaps_obb(&query)).map(|(id, _)| *id).collect::<Vec<usize>>();
+            assert!(!expected.is_empty());
+            assert!(expected.len() < obbs.iter().filter(|(_, o)| o.bounds().overlaps(&query.bounds())).count());
+            assert_eq!(ids(result), expected);
+
+            let expected = quads.iter().filter(|(_, q)| query.overlaps_cell(q)).map(|(id, _)| *id).collect::<Vec<usize>>();
+            assert!(!expected.is_empty());
+            loose += quads.iter().filter(|(_, q)| q.overlaps(&query.bounds())).count() - expected.len();
+            assert_eq!(ids(quadtree.search_obb(&query)), expected);
+        }
+        assert!(loose > 0);
+    }
+
+    #[test]
+    fn obbs_3d() {
+        let (sin, cos) = FRAC_PI_4.sin_cos();
+        let rotated = Obb3::new(0.0, 0.0, 0.0, 10.0, 10.0, 10.0, [[cos, sin, 0.0], [-sin, cos, 0.0], [0.0, 0.0, 1.0]]);
+        assert!(!rotated.overlaps_cell(&Oct::new(12.0, 12.0, 0.0, 2.0, 2.0, 2.0)));
+        assert!(rotated.overlaps_cell(&Oct::new(14.0, 0.0, 9.0, 1.0, 1.0, 1.0)));
+        let tilted = Obb3::new(20.0, 0.0, 0.0, 10.0, 10.0, 10.0, rotation(FRAC_PI_4, FRAC_PI_4));
+        assert!(rotated.bounds().overlaps(&tilted.bounds()));
+        assert!(!rotated.overlaps_obb(&Obb3::new(22.0, 22.0, 0.0, 10.0, 1.0, 1.0, rotation(-FRAC_PI_4, 0.0))));
+        assert!(rotated.overlaps_obb(&tilted));
+
+        let root = Oct::new(0.0, 0.0, 0.0, 500.0, 500.0, 500.0);
+        let mut obbtree = ObbOctTree::new(root);
+        let mut octtree = OctTree::new(root);
+        let (mut obbs, mut octs) = (Vec::new(), Vec::new());
+        for (i, [x, y, z]) in EDGES.iter().flat_map(|edge| (0..30).flat_map(move |step| {
+            let (a, b) = (step as f32 * 33.0 - 478.5, ((step * 7) % 30) as f32 * 33.0 - 478.5);
+            [[*edge, a, b], [b, *edge, a], [a, b, *edge]]
+        })).enumerate() {
+            let obb = Obb3::new(x, y, z, 10.0, 3.0, 2.0, rotation(i as f32 * 0.37, i as f32 * 0.23));
+            let oct = Oct::new(x, y, z, 6.0, 3.0, 2.0);
+            obbtree.insert(i, obb);
+            octtree.insert(i, oct);
+            obbs.push((i, obb));
+            octs.push((i, oct));
+        }
+        assert!(obbtree.validate().is_ok());
+        assert!(octtree.validate().is_ok());
+
+        let mut loose = 0;
+        for query in [Obb3::new(0.0, 0.0, 0.0, 200.0, 30.0, 30.0, rotation(0.6, 0.9)), Obb3::new(125.0, -250.0, 62.5, 60.0, 60.0, 60.0, rotation(1.2, 0.3))] {
+            let result = obbtree.search_obb(&query);
+            assert!(result.iter().all(|(_, o)| o.overlaps_obb(&query)));
+            let expected = obbs.iter().filter(|(_, o)| o.overlaps_obb(&query)).map(|(id, _)| *id).collect::<Vec<usize>>();
+            assert!(!expected.is_empty());
+            assert!(expected.len() < obbs.iter().filter(|(_, o)| o.bounds().overlaps(&query.bounds())).count());
+            assert_eq!(ids(result), expected);
+
+            let expected = octs.iter().filter(|(_, o)| query.overlaps_cell(o)).map(|(id, _)| *id).collect::<Vec<usize>>();
+            assert!(!expected.is_empty());
+            loose += octs.iter().filter(|(_, o)| o.overlaps(&query.bounds())).count() - expected.len();
+            assert_eq!(ids(octtree.search_obb(&query)), expected);
+        }
+        assert!(loose > 0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Dimension, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+use crate::{Dimension, Extents, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
 
 ///3-dimensional cube.
 #[derive(Debug, Clone, Copy)]
@@ -32,10 +32,33 @@ impl Dimension<8> for Oct {
         || (self.z - area.z).abs() > (self.half_z + area.half_z))
     }
     #[inline]
+    fn contains(&self, other: &Self) -> bool {
+        (self.x - other.x).abs() + other.half_x <= self.half_x
+            && (self.y - other.y).abs() + other.half_y <= self.half_y
+            && (self.z - other.z).abs() + other.half_z <= self.half_z
+    }
+    #[inline]
+    fn union(&self, other: &Self) -> Self {
+        let min_x = (self.x - self.half_x).min(other.x - other.half_x);
+        let max_x = (self.x + self.half_x).max(other.x + other.half_x);
+        let min_y = (self.y - self.half_y).min(other.y - other.half_y);
+        let max_y = (self.y + self.half_y).max(other.y + other.half_y);
+        let min_z = (self.z - self.half_z).min(other.z - other.half_z);
+        let max_z = (self.z + self.half_z).max(other.z + other.half_z);
+        Self::new(
+            (min_x + max_x) / 2.0,
+            (min_y + max_y) / 2.0,
+            (min_z + max_z) / 2.0,
+            (max_x - min_x) / 2.0,
+            (max_y - min_y) / 2.0,
+            (max_z - min_z) / 2.0,
+        )
+    }
+    #[inline]
     fn contains_center(&self, point: &Self) -> bool {
-        point.x <= self.x + self.half_x
-            && point.y <= self.y + self.half_y
-            && point.z <= self.z + self.half_z
+        point.x < self.x + self.half_x
+            && point.y < self.y + self.half_y
+            && point.z < self.z + self.half_z
             && point.x >= self.x - self.half_x
             && point.y >= self.y - self.half_y
             && point.z >= self.z - self.half_z
@@ -121,6 +144,28 @@ impl Dimension<8> for Oct {
     }
 }
 
+impl Extents for Oct {
+    const AXES: usize = 3;
+    fn center(&self, axis: usize) -> f32 {
+        [self.x, self.y, self.z][axis]
+    }
+    fn half(&self, axis: usize) -> f32 {
+        [self.half_x, self.half_y, self.half_z][axis]
+    }
+    fn from_extents(center: &[f32], half: &[f32]) -> Self {
+        Self::new(center[0], center[1], center[2], half[0], half[1], half[2])
+    }
+}
+
+impl Shape<Oct> for Oct {
+    fn bounds(&self) -> Oct {
+        *self
+    }
+    fn overlaps_cell(&self, cell: &Oct) -> bool {
+        self.overlaps(cell)
+    }
+}
+
 ///3-dimensional tree representation.
 pub type OctTree<I> = Tree<I, 8, Oct, TNode<I, 8, Oct>>;
 
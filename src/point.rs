@@ -0,0 +1,348 @@
+use crate::{Dimension, Node, Oct, Quad, Tree, Violation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+
+///Position without extents, taking half the memory of a zero-sized dimension.
+pub trait Point: Copy {
+    const AXES: usize;
+    fn coord(&self, axis: usize) -> f32;
+    fn from_coords(coords: &[f32]) -> Self;
+    ///Euclidean distance between the two points.
+    fn distance(&self, other: &Self) -> f32 {
+        (0..Self::AXES).map(|axis| (self.coord(axis) - other.coord(axis)).powi(2)).sum::<f32>().sqrt()
+    }
+}
+
+///1-dimensional point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point1 {
+    pub x: f32,
+}
+
+impl Point1 {
+    pub fn new(x: f32) -> Self {
+        Self { x }
+    }
+}
+
+impl Point for Point1 {
+    const AXES: usize = 1;
+    fn coord(&self, _axis: usize) -> f32 {
+        self.x
+    }
+    fn from_coords(coords: &[f32]) -> Self {
+        Self::new(coords[0])
+    }
+}
+
+///2-dimensional point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point2 {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Point for Point2 {
+    const AXES: usize = 2;
+    fn coord(&self, axis: usize) -> f32 {
+        [self.x, self.y][axis]
+    }
+    fn from_coords(coords: &[f32]) -> Self {
+        Self::new(coords[0], coords[1])
+    }
+}
+
+///3-dimensional point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Point3 {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl Point for Point3 {
+    const AXES: usize = 3;
+    fn coord(&self, axis: usize) -> f32 {
+        [self.x, self.y, self.z][axis]
+    }
+    fn from_coords(coords: &[f32]) -> Self {
+        Self::new(coords[0], coords[1], coords[2])
+    }
+}
+
+///4-dimensional point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Point4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+}
+
+impl Point for Point4 {
+    const AXES: usize = 4;
+    fn coord(&self, axis: usize) -> f32 {
+        [self.x, self.y, self.z, self.w][axis]
+    }
+    fn from_coords(coords: &[f32]) -> Self {
+        Self::new(coords[0], coords[1], coords[2], coords[3])
+    }
+}
+
+///Tree node for point data. Each point lives in exactly one leaf: the child whose cell holds it, with points on a split going to the upper side.
+///Inserted dimensions are reduced to their center and handed back with zero extents.
+#[derive(Debug, Clone)]
+pub struct PointNode<I: Clone, const S: usize, D: Clone + Dimension<S>, P: Point> {
+    space: D,
+    capacity: u8,
+    depth_limit: u8,
+    nodes: Option<Vec<Self>>,
+    points: Vec<(I, P)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, P: Point> PointNode<I, S, D, P> {
+    pub fn new(rect: D, capacity: u8, depth: u8) -> Self {
+        assert_eq!(D::AXES, P::AXES, "Points need as many axes as the dimension");
+        Self {
+            space: rect,
+            capacity: capacity.max(1),
+            depth_limit: depth,
+            nodes: None,
+            points: Vec::new(),
+        }
+    }
+
+    fn point(d: &D) -> P {
+        P::from_coords(&(0..D::AXES).map(|axis| d.center(axis)).collect::<Vec<f32>>())
+    }
+
+    fn dimension(p: &P) -> D {
+        D::from_extents(&(0..P::AXES).map(|axis| p.coord(axis)).collect::<Vec<f32>>(), &vec![0.0; P::AXES])
+    }
+
+    ///Whether the point lies in the node's space, edges included.
+    fn holds(&self, p: &P) -> bool {
+        (0..P::AXES).all(|axis| p.coord(axis) >= self.space.min(axis) && p.coord(axis) <= self.space.max(axis))
+    }
+
+    ///Index of the child whose cell takes the point, following the subdivision order.
+    fn child(&self, p: &P) -> usize {
+        (0..P::AXES).map(|axis| ((p.coord(axis) >= self.space.center(axis)) as usize) << axis).sum()
+    }
+
+    fn insert_point(&mut self, id: I, p: P) {
+        let child = self.child(&p);
+        match &mut self.nodes {
+            Some(nodes) => {
+                nodes[child].insert_point(id, p);
+            }
+            None => {
+                self.points.push((id, p));
+                if self.points.len() > self.capacity as usize && self.depth_limit > 0 {
+                    self.subdivide();
+                }
+            }
+        }
+    }
+
+    ///Push the points inside the area into the buffer.
+    pub fn search_points(&self, area: &D, buffer: &mut Vec<(I, P)>) {
+        if !self.space.overlaps(area) {
+            return;
+        }
+        match &self.nodes {
+            Some(nodes) => nodes.iter().for_each(|n| n.search_points(area, buffer)),
+            None => buffer.extend(self.points.iter().filter(|(_, p)| area.overlaps(&Self::dimension(p))).cloned()),
+        }
+    }
+
+    ///Push the k nearest points found below this node into the candidates, which are kept sorted by distance.
+    fn nearest_into(&self, point: &P, k: usize, candidates: &mut Vec<(f32, (I, P))>) {
+        let worst = match candidates.len() < k {
+            true => f32::INFINITY,
+            false => candidates[k - 1].0,
+        };
+        if self.space.distance(&Self::dimension(point)) > worst {
+            return;
+        }
+        match &self.nodes {
+            Some(nodes) => {
+                let mut order = (0..nodes.len()).collect::<Vec<usize>>();
+                order.sort_by_cached_key(|i| nodes[*i].space.distance(&Self::dimension(point)).to_bits());
+                for i in order {
+                    nodes[i].nearest_into(point, k, candidates);
+                }
+            }
+            None => {
+                for (id, p) in self.points.iter() {
+                    let distance = p.distance(point);
+                    if candidates.len() < k || distance < candidates[k - 1].0 {
+                        let index = candidates.partition_point(|(d, _)| *d <= distance);
+                        candidates.insert(index, (distance, (id.clone(), *p)));
+                        candidates.truncate(k);
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_at(&self, depth: usize, violations: &mut Vec<Violation<D>>) -> usize {
+        for (_, p) in self.points.iter().filter(|(_, p)| !self.holds(p)) {
+            violations.push(Violation::StrayItem { depth, space: self.space.clone(), item: Self::dimension(p) });
+        }
+        let mut found = self.points.len();
+        if let Some(nodes) = &self.nodes {
+            if !self.points.is_empty() {
+                violations.push(Violation::InternalObjects { depth, space: self.space.clone(), objects: self.points.len() });
+            }
+            for (i, n) in nodes.iter().enumerate() {
+                for (_, p) in n.points.iter().filter(|(_, p)| self.child(p) != i) {
+                    violations.push(Violation::StrayItem { depth: depth + 1, space: n.space.clone(), item: Self::dimension(p) });
+                }
+                found += n.validate_at(depth + 1, violations);
+            }
+        }
+        found
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, P: Point> Node<I, S, D> for PointNode<I, S, D, P> {
+    ///Store the center of the dimension. Points outside the root are rejected.
+    fn insert(&mut self, id: I, other: D) -> bool {
+        let p = Self::point(&other);
+        if !self.holds(&p) {
+            return false;
+        }
+        self.insert_point(id, p);
+        true
+    }
+    fn subdivide(&mut self) {
+        if self.nodes.is_some() || self.depth_limit == 0 {
+            return;
+        }
+        let mut nodes = self
+            .space
+            .subdivisions()
+            .into_iter()
+            .map(|space| Self::new(space, self.capacity, self.depth_limit - 1))
+            .collect::<Vec<Self>>();
+        for (id, p) in std::mem::take(&mut self.points) {
+            nodes[self.child(&p)].insert_point(id, p);
+        }
+        self.nodes = Some(nodes);
+    }
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        let mut points = Vec::new();
+        self.search_points(area, &mut points);
+        buffer.extend(points.into_iter().map(|(id, p)| (id, Self::dimension(&p))));
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        if !overlaps(&self.space) {
+            return;
+        }
+        match &self.nodes {
+            Some(nodes) => nodes.iter().for_each(|n| n.search_with(overlaps, buffer)),
+            None => buffer.extend(self.points.iter().map(|(id, p)| (id.clone(), Self::dimension(p))).filter(|(_, d)| overlaps(d))),
+        }
+    }
+    fn clear(&mut self) {
+        self.nodes = None;
+        self.points.clear();
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, violations)
+    }
+}
+
+///Point tree representation over any dimension.
+pub type PointTree<I, const S: usize, D, P> = Tree<I, S, D, PointNode<I, S, D, P>>;
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, P: Point> PointTree<I, S, D, P> {
+    pub fn insert_point(&mut self, id: I, point: P) -> bool {
+        self.insert(id, PointNode::<I, S, D, P>::dimension(&point))
+    }
+    ///Find the points inside the area.
+    pub fn search_points(&self, area: &D) -> Vec<(I, P)> {
+        let mut buffer = Vec::new();
+        self.prime.search_points(area, &mut buffer);
+        buffer
+    }
+    ///Find the k points closest to the point, nearest first.
+    pub fn nearest(&self, point: &P, k: usize) -> Vec<(I, P)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut candidates = Vec::with_capacity(k + 1);
+        self.prime.nearest_into(point, k, &mut candidates);
+        candidates.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+///2-dimensional point tree representation.
+pub type PointQuadTree<I> = PointTree<I, 4, Quad, Point2>;
+
+impl<I: Clone> PointQuadTree<I> {
+    pub fn new(translation: Quad) -> Self {
+        Tree::new_tree(PointNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+}
+
+///3-dimensional point tree representation.
+pub type PointOctTree<I> = PointTree<I, 8, Oct, Point3>;
+
+impl<I: Clone> PointOctTree<I> {
+    pub fn new(translation: Oct) -> Self {
+        Tree::new_tree(PointNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+}
+
+#[cfg(test)]
+mod point_tests {
+    use crate::*;
+
+    #[test]
+    fn pointtree() {
+        let mut quadtree = PointQuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut points = Vec::new();
+        for x in -50..50 {
+            for y in -50..50 {
+                let point = Point2::new(x as f32 * 10.0, y as f32 * 10.0);
+                assert!(quadtree.insert_point(x * 1009 + y * 1013, point));
+                points.push((x * 1009 + y * 1013, point));
+            }
+        }
+        assert!(!quadtree.insert_point(0, Point2::new(501.0, 0.0)));
+        assert_eq!(quadtree.count(), 10000);
+        assert!(quadtree.validate().is_ok());
+
+        let area = Quad::new(0.0, 0.0, 50.0, 50.0);
+        assert_eq!(quadtree.search_points(&area).len(), 121);
+        assert_eq!(quadtree.search(&area).len(), 121);
+
+        let point = Point2::new(3.0, -1.0);
+        let nearest = quadtree.nearest(&point, 10);
+        points.sort_by(|(_, a), (_, b)| a.distance(&point).total_cmp(&b.distance(&point)));
+        let distances = nearest.iter().map(|(_, p)| p.distance(&point)).collect::<Vec<f32>>();
+        let expected = points[..10].iter().map(|(_, p)| p.distance(&point)).collect::<Vec<f32>>();
+        assert_eq!(distances, expected);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{Point2, Quad, QuadTree, Segment, Shape};
+
+///2-dimensional simple polygon, convex or concave, given by its vertices in order. The last vertex connects back to the first.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    pub points: Vec<Point2>,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<Point2>) -> Self {
+        Self { points }
+    }
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points.iter().zip(next).map(|(a, b)| Segment::new(*a, *b))
+    }
+    ///Even-odd test, counting the edges crossed by a ray running from the point towards positive x.
+    pub fn contains_point(&self, point: &Point2) -> bool {
+        self.edges()
+            .filter(|e| (e.start.y > point.y) != (e.end.y > point.y))
+            .filter(|e| point.x < e.start.x + (point.y - e.start.y) / (e.end.y - e.start.y) * (e.end.x - e.start.x))
+            .count()
+            % 2
+            == 1
+    }
+}
+
+impl Shape<Quad> for Polygon {
+    fn bounds(&self) -> Quad {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for p in self.points.iter() {
+            (min_x, min_y, max_x, max_y) = (min_x.min(p.x), min_y.min(p.y), max_x.max(p.x), max_y.max(p.y));
+        }
+        match self.points.is_empty() {
+            true => Quad::new(0.0, 0.0, 0.0, 0.0),
+            false => Quad::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0, (max_x - min_x) / 2.0, (max_y - min_y) / 2.0),
+        }
+    }
+    ///Exact polygon-rectangle test: an edge crosses the rectangle, or the rectangle lies entirely inside the polygon.
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        !self.points.is_empty() && (self.edges().any(|e| e.overlaps_cell(cell)) || self.contains_point(&Point2::new(cell.x, cell.y)))
+    }
+}
+
+impl<I: Clone> QuadTree<I> {
+    ///Find the items overlapping the polygon, skipping every node whose cell it misses.
+    pub fn search_polygon(&self, polygon: &Polygon) -> Vec<(I, Quad)> {
+        self.search_custom(&|cell: &Quad| polygon.overlaps_query(cell)).into_iter().filter(|(_, q)| polygon.overlaps_query(q)).collect()
+    }
+}
+
+#[cfg(test)]
+mod polygon_tests {
+    use crate::*;
+
+    #[test]
+    fn polygons() {
+        let l_shape = Polygon::new([(0.0, 0.0), (100.0, 0.0), (100.0, 20.0), (20.0, 20.0), (20.0, 100.0), (0.0, 100.0)].map(|(x, y)| Point2::new(x, y)).to_vec());
+        assert!(l_shape.contains_point(&Point2::new(10.0, 90.0)));
+        assert!(!l_shape.contains_point(&Point2::new(50.0, 50.0)));
+        assert!(l_shape.overlaps_cell(&Quad::new(10.0, 10.0, 1.0, 1.0)));
+        assert!(l_shape.overlaps_cell(&Quad::new(50.0, 50.0, 100.0, 100.0)));
+        assert!(!l_shape.overlaps_cell(&Quad::new(60.0, 60.0, 30.0, 30.0)));
+
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut items = Vec::new();
+        for x in -50..50 {
+            for y in -50..50 {
+                let item = Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5);
+                quadtree.insert(x * 1009 + y * 1013, item);
+                items.push((x * 1009 + y * 1013, item));
+            }
+        }
+        for polygon in [l_shape, Polygon::new(vec![Point2::new(-200.0, -100.0), Point2::new(150.0, -30.0), Point2::new(-50.0, 240.0)])] {
+            let mut result = quadtree.search_polygon(&polygon).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            let mut expected = items.iter().filter(|(_, item)| polygon.overlaps_query(item)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            result.sort();
+            result.dedup();
+            expected.sort();
+            assert_eq!(result, expected);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Dimension, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+use crate::{Dimension, Extents, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
 
 ///2-dimensional square.
 #[derive(Debug, Clone, Copy)]
@@ -25,11 +25,27 @@ impl Dimension<4> for Quad {
         !( (self.x - area.x).abs() > (self.half_x + area.half_x)
         || (self.y - area.y).abs() > (self.half_y + area.half_y))
     }
+    fn contains(&self, other: &Self) -> bool {
+        (self.x - other.x).abs() + other.half_x <= self.half_x
+            && (self.y - other.y).abs() + other.half_y <= self.half_y
+    }
+    fn union(&self, other: &Self) -> Self {
+        let min_x = (self.x - self.half_x).min(other.x - other.half_x);
+        let max_x = (self.x + self.half_x).max(other.x + other.half_x);
+        let min_y = (self.y - self.half_y).min(other.y - other.half_y);
+        let max_y = (self.y + self.half_y).max(other.y + other.half_y);
+        Self::new(
+            (min_x + max_x) / 2.0,
+            (min_y + max_y) / 2.0,
+            (max_x - min_x) / 2.0,
+            (max_y - min_y) / 2.0,
+        )
+    }
     fn contains_center(&self, point: &Self) -> bool {
         !( point.x >= self.x + self.half_x
         || point.y >= self.y + self.half_y
-        || point.x <= self.x - self.half_x
-        || point.y <= self.y - self.half_y)
+        || point.x < self.x - self.half_x
+        || point.y < self.y - self.half_y)
     }
     fn subdivisions(&self) -> [Self; 4] {
         let half_x = self.half_x / 2.0;
@@ -68,6 +84,28 @@ impl Dimension<4> for Quad {
     }
 }
 
+impl Extents for Quad {
+    const AXES: usize = 2;
+    fn center(&self, axis: usize) -> f32 {
+        [self.x, self.y][axis]
+    }
+    fn half(&self, axis: usize) -> f32 {
+        [self.half_x, self.half_y][axis]
+    }
+    fn from_extents(center: &[f32], half: &[f32]) -> Self {
+        Self::new(center[0], center[1], half[0], half[1])
+    }
+}
+
+impl Shape<Quad> for Quad {
+    fn bounds(&self) -> Quad {
+        *self
+    }
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        self.overlaps(cell)
+    }
+}
+
 ///2-dimensional tree representation.
 pub type QuadTree<I> = Tree<I, 4, Quad, TNode<I, 4, Quad>>;
 
@@ -80,6 +118,7 @@ impl<I: Clone> QuadTree<I> {
 #[cfg(test)]
 mod quadtree_tests {
     use super::*;
+    use crate::{Insertion, QueryMode};
 
     #[test]
     fn quadtree() {
@@ -92,4 +131,50 @@ mod quadtree_tests {
         let result = quadtree.search(&Quad::new(0.0, 0.0, 50.0, 50.0));
         assert_eq!(result.len(), 169);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn quadtree_smallest() {
+        let mut quadtree = QuadTree::new_tree(TNode::with_insertion(Quad::new(0.0, 0.0, 500.0, 500.0), DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH, Insertion::Smallest));
+        for i in 0..500 {
+            quadtree.insert(i, Quad::new(((i * 7919) % 1000) as f32 - 499.7, ((i * 104729) % 1000) as f32 - 499.7, 0.2, 0.2));
+        }
+        let straddlers = [Quad::new(0.0, 37.0, 4.0, 4.0), Quad::new(250.0, 250.0, 2.0, 2.0), Quad::new(-125.0, 300.0, 3.0, 1.0)];
+        for (i, straddler) in straddlers.iter().enumerate() {
+            quadtree.insert(500 + i as i32, *straddler);
+        }
+        assert_eq!(quadtree.count(), 503);
+        assert!(quadtree.validate().is_ok());
+        assert_eq!(quadtree.stats().stored, 503);
+
+        for (i, straddler) in straddlers.iter().enumerate() {
+            let result = quadtree.search(straddler);
+            assert_eq!(result.iter().filter(|(id, _)| *id == 500 + i as i32).count(), 1);
+            assert_eq!(quadtree.freeze().search(straddler).len(), result.len());
+        }
+    }
+
+    #[test]
+    fn quadtree_modes() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut items = Vec::new();
+        for i in 0..1000 {
+            let item = Quad::new(((i * 7919) % 1000) as f32 - 500.0, ((i * 104729) % 1000) as f32 - 500.0, (i % 20) as f32 + 0.5, (i % 15) as f32 + 0.5);
+            quadtree.insert(i, item);
+            items.push((i, item));
+        }
+        quadtree.insert(1000, Quad::new(0.0, 0.0, 60.0, 60.0));
+        items.push((1000, Quad::new(0.0, 0.0, 60.0, 60.0)));
+
+        let area = Quad::new(0.0, 0.0, 200.0, 200.0);
+        let point = Quad::new(3.0, 4.0, 0.0, 0.0);
+        assert!(area.within(&Quad::new(10.0, 0.0, 210.0, 200.0)));
+        assert!(!area.within(&Quad::new(10.0, 0.0, 209.0, 200.0)));
+        for (query, mode) in [(area, QueryMode::Within), (area, QueryMode::Intersects), (point, QueryMode::Contains)] {
+            let mut result = quadtree.search_mode(&query, mode).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+            let expected = items.iter().filter(|(_, item)| item.relates(&query, mode)).map(|(id, _)| *id).collect::<Vec<i32>>();
+            result.sort();
+            assert!(!expected.is_empty());
+            assert_eq!(result, expected);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Classification, Dimension, Insertion, TNode, Tree};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+///Region built from areas with union (`|`), intersection (`&`), complement (`!`) and difference (`-`).
+#[derive(Debug, Clone)]
+pub enum Query<D> {
+    Area(D),
+    And(Box<Query<D>>, Box<Query<D>>),
+    Or(Box<Query<D>>, Box<Query<D>>),
+    Not(Box<Query<D>>),
+}
+
+impl<D> Query<D> {
+    pub fn area(area: D) -> Self {
+        Self::Area(area)
+    }
+    ///Whether the dimension lies inside, partly inside or outside the region.
+    pub fn classify<const S: usize>(&self, d: &D) -> Classification
+    where
+        D: Dimension<S>,
+    {
+        use Classification::*;
+        match self {
+            Self::Area(area) if d.within(area) => Inside,
+            Self::Area(area) if d.overlaps(area) => Intersecting,
+            Self::Area(_) => Outside,
+            Self::And(a, b) => match (a.classify(d), b.classify(d)) {
+                (Outside, _) | (_, Outside) => Outside,
+                (Inside, Inside) => Inside,
+                _ => Intersecting,
+            },
+            Self::Or(a, b) => match (a.classify(d), b.classify(d)) {
+                (Inside, _) | (_, Inside) => Inside,
+                (Outside, Outside) => Outside,
+                _ => Intersecting,
+            },
+            Self::Not(a) => match a.classify(d) {
+                Inside => Outside,
+                Intersecting => Intersecting,
+                Outside => Inside,
+            },
+        }
+    }
+    ///First point of the dimension's part inside the clip that lies in the region, or None when they don't meet. The region only
+    ///changes at the edges of its areas, so trying those edges and the midpoints between them along every axis is exact.
+    fn locate<const S: usize>(&self, d: &D, clip: &D) -> Option<Vec<f32>>
+    where
+        D: Dimension<S>,
+    {
+        let lowest = (0..D::AXES).map(|axis| d.min(axis).max(clip.min(axis))).collect::<Vec<f32>>();
+        match self.classify(d) {
+            Classification::Outside => return None,
+            Classification::Inside => return Some(lowest),
+            Classification::Intersecting => {}
+        }
+        let mut candidates = Vec::with_capacity(D::AXES);
+        for (axis, low) in lowest.iter().copied().enumerate() {
+            let high = d.max(axis).min(clip.max(axis));
+            if low > high {
+                return None;
+            }
+            let mut edges = vec![low, high];
+            self.edges(axis, &mut edges);
+            edges.retain(|e| low <= *e && *e <= high);
+            edges.sort_by(f32::total_cmp);
+            edges.dedup();
+            let mut points = vec![low];
+            for pair in edges.windows(2) {
+                points.push((pair[0] + pair[1]) / 2.0);
+                points.push(pair[1]);
+            }
+            candidates.push(points);
+        }
+        let mut indices = vec![0; D::AXES];
+        loop {
+            let point = (0..D::AXES).map(|axis| candidates[axis][indices[axis]]).collect::<Vec<f32>>();
+            if self.holds(&point) {
+                return Some(point);
+            }
+            let axis = (0..D::AXES).rev().find(|axis| indices[*axis] + 1 < candidates[*axis].len())?;
+            indices[axis] += 1;
+            indices[axis + 1..].iter_mut().for_each(|i| *i = 0);
+        }
+    }
+
+    ///Whether the point lies in the region, counting the edges of every area as part of it.
+    fn holds<const S: usize>(&self, point: &[f32]) -> bool
+    where
+        D: Dimension<S>,
+    {
+        match self {
+            Self::Area(area) => (0..D::AXES).all(|axis| area.min(axis) <= point[axis] && point[axis] <= area.max(axis)),
+            Self::And(a, b) => a.holds(point) && b.holds(point),
+            Self::Or(a, b) => a.holds(point) || b.holds(point),
+            Self::Not(a) => !a.holds(point),
+        }
+    }
+
+    fn edges<const S: usize>(&self, axis: usize, edges: &mut Vec<f32>)
+    where
+        D: Dimension<S>,
+    {
+        match self {
+            Self::Area(area) => edges.extend([area.min(axis), area.max(axis)]),
+            Self::And(a, b) | Self::Or(a, b) => {
+                a.edges(axis, edges);
+                b.edges(axis, edges);
+            }
+            Self::Not(a) => a.edges(axis, edges),
+        }
+    }
+}
+
+impl<D> BitAnd for Query<D> {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+}
+
+impl<D> BitOr for Query<D> {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+}
+
+impl<D> Not for Query<D> {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+impl<D> Sub for Query<D> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self & !other
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    ///Find the items overlapping the region, each once, skipping nodes it fully excludes. Items of partly covered nodes are tested
+    ///exactly, and a copied item is only reported by the leaf holding the first point where it meets the region.
+    pub fn search_query(&self, query: &Query<D>) -> Vec<(I, D)> {
+        let root = &self.prime.space;
+        let mut buffer = Vec::new();
+        self.prime.cull(
+            &|d| query.classify(d),
+            &|n, o, node| match n.insertion {
+                Insertion::Smallest => node == Classification::Inside || query.locate(o, root).is_some(),
+                Insertion::Duplicate => query.locate(o, root).is_some_and(|p| n.holds(root, |axis| p[axis])),
+            },
+            &mut buffer,
+        );
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod query_tests {
+    use crate::*;
+
+    ///Whether the part of the item inside the area isn't covered by the cut.
+    fn overlaps_minus(item: &Quad, area: &Quad, cut: &Quad) -> bool {
+        let (min_x, max_x) = (item.min(0).max(area.min(0)), item.max(0).min(area.max(0)));
+        let (min_y, max_y) = (item.min(1).max(area.min(1)), item.max(1).min(area.max(1)));
+        min_x <= max_x && min_y <= max_y && !Quad::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0, (max_x - min_x) / 2.0, (max_y - min_y) / 2.0).within(cut)
+    }
+
+    fn sorted_ids(result: Vec<(i32, Quad)>) -> Vec<i32> {
+        let mut ids = result.into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn search_query() {
+        let (a, b, c) = (Quad::new(-100.0, 0.0, 80.0, 80.0), Quad::new(200.0, 100.0, 80.0, 60.0), Quad::new(0.0, 20.0, 40.0, 40.0));
+        let query = (Query::area(a) | Query::area(b)) - Query::area(c);
+        assert_eq!(query.classify(&Quad::new(-150.0, 0.0, 10.0, 10.0)), Classification::Inside);
+        assert_eq!(query.classify(&Quad::new(0.0, 20.0, 10.0, 10.0)), Classification::Outside);
+        assert_eq!(query.classify(&Quad::new(-40.0, 20.0, 10.0, 10.0)), Classification::Intersecting);
+
+        let root = Quad::new(0.0, 0.0, 500.0, 500.0);
+        let mut duplicate = QuadTree::new(root);
+        let mut smallest = QuadTree::new_tree(TNode::with_insertion(root, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH, Insertion::Smallest));
+        let mut items = Vec::new();
+        for x in -15..15 {
+            for y in -15..15 {
+                items.push(Quad::new(x as f32 * 33.0 + 7.0, y as f32 * 33.0 + 7.0, 2.0, 2.0));
+            }
+        }
+        for (i, area) in [a, b, c].iter().enumerate() {
+            for step in 0..20 {
+                let t = step as f32 / 19.0;
+                let size = (step % 6) as f32 + 1.0;
+                let (x, y) = (area.min(0) + t * area.half_x * 2.0, area.min(1) + t * area.half_y * 2.0);
+                items.push(Quad::new(x, area.min(1), size, size + i as f32));
+                items.push(Quad::new(x, area.max(1), size + i as f32, size));
+                items.push(Quad::new(area.min(0), y, size, size));
+                items.push(Quad::new(area.max(0), y, size, 0.0));
+            }
+        }
+        let straddler = Quad::new(10.0, 5.0, 40.0, 5.0);
+        let big = Quad::new(-100.0, -200.0, 150.0, 150.0);
+        items.extend([straddler, big]);
+        for (id, item) in items.iter().enumerate() {
+            duplicate.insert(id as i32, *item);
+            smallest.insert(id as i32, *item);
+        }
+        assert!(!overlaps_minus(&straddler, &a, &c) && !overlaps_minus(&straddler, &b, &c));
+        assert_eq!(query.classify(&straddler), Classification::Intersecting);
+
+        let expected = (0..items.len() as i32).filter(|id| overlaps_minus(&items[*id as usize], &a, &c) || overlaps_minus(&items[*id as usize], &b, &c)).collect::<Vec<i32>>();
+        assert!(expected.len() > 100);
+        for tree in [&duplicate, &smallest] {
+            let result = sorted_ids(tree.search_query(&query));
+            assert_eq!(result, expected);
+            assert!(!result.contains(&(items.len() as i32 - 2)));
+        }
+
+        let area = Query::area(Quad::new(-100.0, -200.0, 20.0, 20.0));
+        assert!(duplicate.search(&big).iter().filter(|(_, o)| o.half_x == 150.0).count() > 1);
+        assert_eq!(sorted_ids(duplicate.search_query(&area)), sorted_ids(duplicate.search_mode(&Quad::new(-100.0, -200.0, 20.0, 20.0), QueryMode::Intersects)));
+        assert_eq!(duplicate.search_query(&area).iter().filter(|(_, o)| o.half_x == 150.0).count(), 1);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{enclose, validate::encloses, Dimension, Extents, Node, Tree, Violation, DEFAULT_CAPACITY};
+
+///R-tree node, grouping items under bounding dimensions that grow to fit them. Overflowing nodes are split with the R*-tree heuristics.
+#[derive(Debug, Clone)]
+pub struct RTreeNode<I: Clone, const S: usize, D: Clone + Dimension<S>> {
+    bounds: Option<D>,
+    capacity: u8,
+    minimum: u8,
+    ///Number of levels below this node. Leaves are at height 0 and hold objects, every other node holds nodes.
+    height: u8,
+    nodes: Vec<Self>,
+    objects: Vec<(I, D)>,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> RTreeNode<I, S, D> {
+    ///Create an empty root. Nodes hold at most `capacity` entries and, apart from the root, at least 40% of that.
+    pub fn new(capacity: u8) -> Self {
+        Self::empty(capacity.max(4), 0)
+    }
+    ///The smallest dimension enclosing everything stored below the node. None while the node is empty.
+    pub fn bounds(&self) -> Option<&D> {
+        self.bounds.as_ref()
+    }
+
+    fn empty(capacity: u8, height: u8) -> Self {
+        Self {
+            bounds: None,
+            capacity,
+            minimum: (capacity as usize * 2 / 5).max(1) as u8,
+            height,
+            nodes: Vec::new(),
+            objects: Vec::new(),
+        }
+    }
+
+    ///Insert below this node, returning the new sibling when the node had to be split.
+    fn insert_entry(&mut self, id: I, other: D) -> Option<Self> {
+        self.bounds = Some(match &self.bounds {
+            Some(bounds) => bounds.union(&other),
+            None => other.clone(),
+        });
+        if self.height == 0 {
+            self.objects.push((id, other));
+            if self.objects.len() > self.capacity as usize {
+                let objects = split(&mut self.objects, self.minimum as usize, |(_, o)| o);
+                return Some(self.sibling(Vec::new(), objects));
+            }
+        } else {
+            let child = self.choose_subtree(&other);
+            if let Some(sibling) = self.nodes[child].insert_entry(id, other) {
+                self.nodes.push(sibling);
+                if self.nodes.len() > self.capacity as usize {
+                    let nodes = split(&mut self.nodes, self.minimum as usize, |n| n.bounds.as_ref().unwrap());
+                    return Some(self.sibling(nodes, Vec::new()));
+                }
+            }
+        }
+        None
+    }
+
+    ///Build the other half of a split and shrink this node's bounds to what it kept.
+    fn sibling(&mut self, nodes: Vec<Self>, objects: Vec<(I, D)>) -> Self {
+        self.bounds = enclose(self.nodes.iter().filter_map(|n| n.bounds.as_ref()).chain(self.objects.iter().map(|(_, o)| o)));
+        let mut sibling = Self::empty(self.capacity, self.height);
+        sibling.bounds = enclose(nodes.iter().filter_map(|n| n.bounds.as_ref()).chain(objects.iter().map(|(_, o)| o)));
+        sibling.nodes = nodes;
+        sibling.objects = objects;
+        sibling
+    }
+
+    ///Pick the child needing the least overlap enlargement when its children are leaves, and the least area enlargement otherwise.
+    fn choose_subtree(&self, other: &D) -> usize {
+        let bounds = self.nodes.iter().map(|n| n.bounds.clone().unwrap()).collect::<Vec<D>>();
+        let cost = |i: usize| {
+            let grown = bounds[i].union(other);
+            let enlargement = area(&grown) - area(&bounds[i]);
+            let overlap = match self.height {
+                1 => (0..bounds.len())
+                    .filter(|j| *j != i)
+                    .map(|j| overlap(&grown, &bounds[j]) - overlap(&bounds[i], &bounds[j]))
+                    .sum(),
+                _ => 0.0,
+            };
+            (overlap, enlargement, area(&bounds[i]))
+        };
+        let costs = (0..bounds.len()).map(cost).collect::<Vec<(f32, f32, f32)>>();
+        (1..costs.len()).fold(0, |best, i| if costs[i] < costs[best] { i } else { best })
+    }
+
+    fn validate_at(&self, depth: usize, root: bool, violations: &mut Vec<Violation<D>>) -> usize {
+        let entries = self.nodes.len() + self.objects.len();
+        let space = match &self.bounds {
+            Some(bounds) => bounds.clone(),
+            None => {
+                if entries > 0 {
+                    violations.push(Violation::Bounds { depth, space: enclose(self.objects.iter().map(|(_, o)| o)).unwrap() });
+                }
+                return self.objects.len();
+            }
+        };
+        if entries > self.capacity as usize || (!root && entries < self.minimum as usize) {
+            violations.push(Violation::Fill { depth, space: space.clone(), entries });
+        }
+        if (self.height == 0) != self.nodes.is_empty() || (self.height > 0 && !self.objects.is_empty()) {
+            violations.push(Violation::DepthLimit { depth, space: space.clone() });
+        }
+        for (_, item) in self.objects.iter().filter(|(_, o)| !encloses(&space, o)) {
+            violations.push(Violation::StrayItem { depth, space: space.clone(), item: item.clone() });
+        }
+        let mut found = self.objects.len();
+        for n in self.nodes.iter() {
+            if self.height.checked_sub(1) != Some(n.height) {
+                violations.push(Violation::DepthLimit { depth: depth + 1, space: space.clone() });
+            }
+            if n.bounds.as_ref().is_some_and(|b| !encloses(&space, b)) {
+                violations.push(Violation::Bounds { depth: depth + 1, space: space.clone() });
+            }
+            found += n.validate_at(depth + 1, false, violations);
+        }
+        found
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for RTreeNode<I, S, D> {
+    fn insert(&mut self, id: I, other: D) -> bool {
+        if let Some(split) = self.insert_entry(id, other) {
+            let old = std::mem::replace(self, Self::empty(self.capacity, self.height + 1));
+            self.bounds = enclose([&old, &split].into_iter().filter_map(|n| n.bounds.as_ref()));
+            self.nodes = vec![old, split];
+        }
+        true
+    }
+    ///R-tree nodes split themselves as they fill up, so there is nothing to subdivide.
+    fn subdivide(&mut self) {}
+    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+        if self.bounds.as_ref().is_some_and(|b| b.overlaps(area)) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| o.overlaps(area)).cloned());
+            for n in self.nodes.iter() {
+                n.search(area, buffer);
+            }
+        }
+    }
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    where
+        OF: Fn(&D) -> bool,
+    {
+        if self.bounds.as_ref().is_some_and(overlaps) {
+            buffer.extend(self.objects.iter().filter(|(_, o)| overlaps(o)).cloned());
+            for n in self.nodes.iter() {
+                n.search_with(overlaps, buffer);
+            }
+        }
+    }
+    fn clear(&mut self) {
+        *self = Self::empty(self.capacity, 0);
+    }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, true, violations)
+    }
+}
+
+///Split off part of the entries using the R*-tree heuristics. The split axis is the one whose candidate distributions have the smallest total margin,
+///and along it the distribution with the least overlap between the two groups wins, ties going to the smallest total area.
+fn split<E, const S: usize, D: Clone + Dimension<S>, B>(entries: &mut Vec<E>, minimum: usize, bounds: B) -> Vec<E>
+where
+    B: Fn(&E) -> &D,
+{
+    let len = entries.len();
+    let minimum = minimum.min(len / 2).max(1);
+    let groups = |order: &[usize], k: usize| {
+        let first = enclose(order[..k].iter().map(|i| bounds(&entries[*i]))).unwrap();
+        let second = enclose(order[k..].iter().map(|i| bounds(&entries[*i]))).unwrap();
+        (first, second)
+    };
+    let orders = |axis: usize| {
+        let mut by_min = (0..len).collect::<Vec<usize>>();
+        by_min.sort_by(|a, b| bounds(&entries[*a]).min(axis).total_cmp(&bounds(&entries[*b]).min(axis)));
+        let mut by_max = (0..len).collect::<Vec<usize>>();
+        by_max.sort_by(|a, b| bounds(&entries[*a]).max(axis).total_cmp(&bounds(&entries[*b]).max(axis)));
+        [by_min, by_max]
+    };
+    let mut best_axis = (f32::INFINITY, 0);
+    for axis in 0..D::AXES {
+        let margins = orders(axis)
+            .iter()
+            .flat_map(|order| (minimum..=len - minimum).map(|k| groups(order, k)).collect::<Vec<(D, D)>>())
+            .map(|(first, second)| margin(&first) + margin(&second))
+            .sum::<f32>();
+        if margins < best_axis.0 {
+            best_axis = (margins, axis);
+        }
+    }
+    let mut best = ((f32::INFINITY, f32::INFINITY), Vec::new(), 0);
+    for order in orders(best_axis.1) {
+        for k in minimum..=len - minimum {
+            let (first, second) = groups(&order, k);
+            let cost = (overlap(&first, &second), area(&first) + area(&second));
+            if cost < best.0 {
+                best = (cost, order.clone(), k);
+            }
+        }
+    }
+    let (_, order, k) = best;
+    let mut taken = entries.drain(..).map(Some).collect::<Vec<Option<E>>>();
+    let mut ordered = order.iter().map(|i| taken[*i].take().unwrap()).collect::<Vec<E>>();
+    let second = ordered.split_off(k);
+    *entries = ordered;
+    second
+}
+
+fn area<D: Extents>(d: &D) -> f32 {
+    (0..D::AXES).map(|axis| d.half(axis) * 2.0).product()
+}
+
+fn margin<D: Extents>(d: &D) -> f32 {
+    (0..D::AXES).map(|axis| d.half(axis) * 2.0).sum()
+}
+
+fn overlap<D: Extents>(a: &D, b: &D) -> f32 {
+    (0..D::AXES).map(|axis| (a.max(axis).min(b.max(axis)) - a.min(axis).max(b.min(axis))).max(0.0)).product()
+}
+
+///R-tree representation over any dimension.
+pub type RTree<I, const S: usize, D> = Tree<I, S, D, RTreeNode<I, S, D>>;
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> RTree<I, S, D> {
+    pub fn new() -> Self {
+        Tree::new_tree(RTreeNode::new(DEFAULT_CAPACITY))
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Default for RTree<I, S, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod rtree_tests {
+    use crate::*;
+
+    #[test]
+    fn rtree() {
+        let mut rtree = RTree::new();
+        let mut items = Vec::new();
+        for i in 0..2000 {
+            let x = ((i * 7919) % 2000) as f32 - 1000.0;
+            let y = ((i * 104729) % 500) as f32;
+            let item = Quad::new(x, y, (i % 40) as f32 + 0.5, (i % 8) as f32 / 4.0 + 0.25);
+            rtree.insert(i, item);
+            items.push((i, item));
+        }
+        assert_eq!(rtree.count(), 2000);
+        assert!(rtree.validate().is_ok());
+        assert!(rtree.prime().bounds().is_some());
+
+        let area = Quad::new(-300.0, 200.0, 80.0, 20.0);
+        let mut result = rtree.search(&area).into_iter().map(|(id, _)| id).collect::<Vec<i32>>();
+        let expected = items.iter().filter(|(_, item)| item.overlaps(&area)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        result.sort();
+        assert_eq!(result, expected);
+
+        rtree.clear();
+        assert!(rtree.search(&area).is_empty());
+        assert!(rtree.validate().is_ok());
+    }
+}
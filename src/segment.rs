@@ -0,0 +1,106 @@
+use crate::{Extents, Point, Point2, Quad, Shape};
+
+///2-dimensional line segment between two points.
+#[derive(Debug, Clone, Copy)]
+pub struct Segment {
+    pub start: Point2,
+    pub end: Point2,
+}
+
+impl Segment {
+    pub fn new(start: Point2, end: Point2) -> Self {
+        Self { start, end }
+    }
+    ///Euclidean distance from the point to the closest point of the segment.
+    pub fn distance_to(&self, point: &Point2) -> f32 {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let length = dx * dx + dy * dy;
+        let t = match length > 0.0 {
+            true => (((point.x - self.start.x) * dx + (point.y - self.start.y) * dy) / length).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        point.distance(&Point2::new(self.start.x + t * dx, self.start.y + t * dy))
+    }
+    ///Euclidean distance from the area to the closest point of the segment, which is zero when they overlap.
+    pub fn distance_to_area(&self, area: &Quad) -> f32 {
+        if self.overlaps_cell(area) {
+            return 0.0;
+        }
+        let ends = [self.start, self.end].map(|p| area.distance(&Quad::new(p.x, p.y, 0.0, 0.0)));
+        let corners = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(x, y)| {
+            let corner = Point2::new([area.min(0), area.max(0)][x], [area.min(1), area.max(1)][y]);
+            self.distance_to(&corner)
+        });
+        ends.into_iter().chain(corners).fold(f32::INFINITY, f32::min)
+    }
+}
+
+impl Shape<Quad> for Segment {
+    fn bounds(&self) -> Quad {
+        let (min_x, max_x) = (self.start.x.min(self.end.x), self.start.x.max(self.end.x));
+        let (min_y, max_y) = (self.start.y.min(self.end.y), self.start.y.max(self.end.y));
+        Quad::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0, (max_x - min_x) / 2.0, (max_y - min_y) / 2.0)
+    }
+    ///Exact segment-box test: the segment's start plus its direction enters the box before reaching the end.
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        let direction = [self.end.x - self.start.x, self.end.y - self.start.y];
+        cell.ray_interval(&[self.start.x, self.start.y], &direction).is_some_and(|(near, _)| near <= 1.0)
+    }
+}
+
+///2-dimensional capsule: every point within the radius of a segment.
+#[derive(Debug, Clone, Copy)]
+pub struct Capsule {
+    pub segment: Segment,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(start: Point2, end: Point2, radius: f32) -> Self {
+        Self { segment: Segment::new(start, end), radius }
+    }
+}
+
+impl Shape<Quad> for Capsule {
+    fn bounds(&self) -> Quad {
+        let bounds = self.segment.bounds();
+        Quad::new(bounds.x, bounds.y, bounds.half_x + self.radius, bounds.half_y + self.radius)
+    }
+    fn overlaps_cell(&self, cell: &Quad) -> bool {
+        self.segment.distance_to_area(cell) <= self.radius
+    }
+}
+
+#[cfg(test)]
+mod segment_tests {
+    use crate::{testing::ids, *};
+
+    #[test]
+    fn segments_and_capsules() {
+        let root = Quad::new(0.0, 0.0, 500.0, 500.0);
+        let mut segments = Tree::new_tree(TNode::<_, 4, Quad, Segment>::new(root, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH));
+        let mut capsules = Tree::new_tree(TNode::<_, 4, Quad, Capsule>::new(root, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH));
+        let mut items = Vec::new();
+        for i in 0..2000 {
+            let start = Point2::new(((i * 7919) % 980) as f32 - 489.7, ((i * 104729) % 980) as f32 - 489.7);
+            let end = Point2::new(start.x + ((i * 31) % 21) as f32 - 10.0, start.y + ((i * 17) % 21) as f32 - 10.0);
+            segments.insert(i, Segment::new(start, end));
+            capsules.insert(i, Capsule::new(start, end, 3.0));
+            items.push((i, Segment::new(start, end), Capsule::new(start, end, 3.0)));
+        }
+        assert!(segments.validate().is_ok());
+        assert!(capsules.validate().is_ok());
+
+        let diagonal = Segment::new(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0));
+        let off = Quad::new(9.0, 1.0, 1.0, 1.0);
+        assert!(diagonal.bounds().overlaps(&off));
+        assert!(!diagonal.overlaps_query(&off));
+        assert!((diagonal.distance_to_area(&off) - 18.0f32.sqrt()).abs() < 1e-5);
+
+        let area = Quad::new(10.0, -20.0, 100.0, 60.0);
+        let expected = items.iter().filter(|(_, s, _)| s.overlaps_query(&area)).map(|(id, _, _)| *id).collect::<Vec<i32>>();
+        assert_eq!(ids(segments.search_exact(&area)), expected);
+        let expected = items.iter().filter(|(_, _, c)| c.overlaps_query(&area)).map(|(id, _, _)| *id).collect::<Vec<i32>>();
+        assert_eq!(ids(capsules.search_exact(&area)), expected);
+    }
+}
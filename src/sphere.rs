@@ -0,0 +1,94 @@
+use crate::{Extents, Oct, OctTree, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+
+///3-dimensional sphere, stored in trees subdividing on `Oct` cells.
+#[derive(Debug, Clone, Copy)]
+pub struct Sphere {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(x: f32, y: f32, z: f32, radius: f32) -> Self {
+        Self { x, y, z, radius }
+    }
+    pub fn overlaps_sphere(&self, other: &Sphere) -> bool {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2) <= (self.radius + other.radius).powi(2)
+    }
+}
+
+impl Shape<Oct> for Sphere {
+    fn bounds(&self) -> Oct {
+        Oct::new(self.x, self.y, self.z, self.radius, self.radius, self.radius)
+    }
+    ///Exact sphere-box test: the box's closest point to the center lies within the radius.
+    fn overlaps_cell(&self, cell: &Oct) -> bool {
+        cell.distance(&Oct::new(self.x, self.y, self.z, 0.0, 0.0, 0.0)) <= self.radius
+    }
+}
+
+///3-dimensional tree of spheres.
+pub type SphereTree<I> = Tree<I, 8, Oct, TNode<I, 8, Oct, Sphere>, Sphere>;
+
+impl<I: Clone> SphereTree<I> {
+    pub fn new(translation: Oct) -> Self {
+        Tree::new_tree(TNode::new(translation, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH))
+    }
+    ///Find the spheres overlapping the sphere.
+    pub fn search_sphere(&self, sphere: &Sphere) -> Vec<(I, Sphere)> {
+        self.search(&sphere.bounds()).into_iter().filter(|(_, s)| s.overlaps_sphere(sphere)).collect()
+    }
+}
+
+impl<I: Clone> OctTree<I> {
+    ///Find the items overlapping the sphere.
+    pub fn search_sphere(&self, sphere: &Sphere) -> Vec<(I, Oct)> {
+        self.search(&sphere.bounds()).into_iter().filter(|(_, o)| sphere.overlaps_query(o)).collect()
+    }
+}
+
+#[cfg(test)]
+mod sphere_tests {
+    use crate::{testing::ids, *};
+
+    #[test]
+    fn spheretree() {
+        let sphere = Sphere::new(0.0, 0.0, 0.0, 10.0);
+        assert!(sphere.overlaps_sphere(&Sphere::new(0.0, 12.0, 16.0, 10.0)));
+        assert!(!sphere.overlaps_sphere(&Sphere::new(0.0, 12.0, 16.0, 9.9)));
+        let corner = Oct::new(9.0, 9.0, 9.0, 1.0, 1.0, 1.0);
+        assert!(sphere.bounds().overlaps(&corner));
+        assert!(!sphere.overlaps_cell(&corner));
+        assert!(sphere.overlaps_cell(&Oct::new(10.5, 0.0, 0.0, 0.5, 0.5, 0.5)));
+        assert!(sphere.overlaps_cell(&Oct::new(6.0, 6.0, 0.5, 1.0, 1.0, 1.0)));
+
+        let root = Oct::new(0.0, 0.0, 0.0, 500.0, 500.0, 500.0);
+        let mut spheretree = SphereTree::new(root);
+        let mut octtree = OctTree::new(root);
+        let mut spheres = Vec::new();
+        let mut boxes = Vec::new();
+        for i in 0..2000 {
+            let (x, y, z) = (((i * 7919) % 1000) as f32 - 499.7, ((i * 104729) % 1000) as f32 - 499.7, ((i * 6007) % 1000) as f32 - 499.7);
+            let size = (i % 12) as f32 + 0.5;
+            spheretree.insert(i, Sphere::new(x, y, z, size));
+            octtree.insert(i, Oct::new(x, y, z, size, size, size));
+            spheres.push((i, Sphere::new(x, y, z, size)));
+            boxes.push((i, Oct::new(x, y, z, size, size, size)));
+        }
+        assert!(spheretree.validate().is_ok());
+
+        let query = Sphere::new(-30.0, 20.0, 10.0, 150.0);
+        let result = spheretree.search_sphere(&query);
+        assert!(result.iter().all(|(_, s)| s.overlaps_sphere(&query)));
+        let expected = spheres.iter().filter(|(_, s)| s.overlaps_sphere(&query)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        assert!(!expected.is_empty());
+        assert_eq!(ids(result), expected);
+
+        let result = octtree.search_sphere(&query);
+        assert!(result.iter().all(|(_, o)| query.overlaps_cell(o)));
+        let expected = boxes.iter().filter(|(_, o)| query.overlaps_cell(o)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        assert!(expected.len() < boxes.iter().filter(|(_, o)| query.bounds().overlaps(o)).count());
+        assert_eq!(ids(result), expected);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Dimension, Insertion, TNode, Tree};
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> TNode<I, S, D> {
+    ///Visit the nodes the segment origin + t * direction, t from 0 to 1, passes through in order along it, pushing the items it crosses along with their entry t.
+    ///A copied item is only pushed by the leaf holding the point where the segment enters both it and the root, which the segment enters at `start`.
+    fn segment_into(&self, root: &D, start: f32, origin: &[f32], direction: &[f32], buffer: &mut Vec<(I, D, f32)>) {
+        for (id, o) in self.objects.iter() {
+            if let Some((near, _)) = o.ray_interval(origin, direction).filter(|(near, _)| *near <= 1.0) {
+                let t = near.max(start);
+                if self.insertion == Insertion::Smallest || self.holds(root, |axis| (origin[axis] + t * direction[axis]).clamp(root.min(axis), root.max(axis))) {
+                    buffer.push((id.clone(), o.clone(), near));
+                }
+            }
+        }
+        if let Some(nodes) = &self.nodes {
+            let mut crossed = nodes
+                .iter()
+                .filter_map(|n| n.space.ray_interval(origin, direction).filter(|(near, _)| *near <= 1.0).map(|(near, _)| (near, n)))
+                .collect::<Vec<(f32, &Self)>>();
+            crossed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            for (_, n) in crossed {
+                n.segment_into(root, start, origin, direction, buffer);
+            }
+        }
+    }
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    ///Every item crossed by the segment from a to b, once each, ordered by the parameter t from 0 at a to 1 at b where the segment enters them.
+    ///Only the nodes the segment passes through are visited.
+    pub fn segment_query(&self, a: &[f32], b: &[f32]) -> Vec<(I, D, f32)> {
+        let direction = a.iter().zip(b.iter()).map(|(a, b)| b - a).collect::<Vec<f32>>();
+        let mut buffer = Vec::new();
+        if let Some((start, _)) = self.prime.space.ray_interval(a, &direction).filter(|(near, _)| *near <= 1.0) {
+            self.prime.segment_into(&self.prime.space, start, a, &direction, &mut buffer);
+        }
+        buffer.sort_by(|a, b| a.2.total_cmp(&b.2));
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod stab_tests {
+    use crate::*;
+
+    #[test]
+    fn segment_query() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let mut octree = OctTree::new(Oct::new(0.0, 0.0, 0.0, 500.0, 500.0, 500.0));
+        let mut squares = Vec::new();
+        for i in 0..3000 {
+            let x = ((i * 7919) % 1000) as f32 - 499.7;
+            let y = ((i * 104729) % 1000) as f32 - 499.7;
+            let size = (i % 7) as f32 + 0.5;
+            quadtree.insert(i, Quad::new(x, y, size, size));
+            octree.insert(i, Oct::new(x, y, 0.3, size, size, size));
+            squares.push((i, Quad::new(x, y, size, size)));
+        }
+        let (a, b) = ([-480.0, -300.0], [450.0, 410.0]);
+        let hits = quadtree.segment_query(&a, &b);
+        assert!(hits.windows(2).all(|w| w[0].2 <= w[1].2));
+        let mut result = hits.iter().map(|(id, _, _)| *id).collect::<Vec<i32>>();
+        result.sort();
+        let direction = [b[0] - a[0], b[1] - a[1]];
+        let expected = squares.iter().filter(|(_, q)| q.ray_interval(&a, &direction).is_some_and(|(near, _)| near <= 1.0)).map(|(id, _)| *id).collect::<Vec<i32>>();
+        assert!(!expected.is_empty());
+        assert_eq!(result, expected);
+
+        let mut result = octree.segment_query(&[a[0], a[1], 0.0], &[b[0], b[1], 0.0]).into_iter().map(|(id, _, _)| id).collect::<Vec<i32>>();
+        result.sort();
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Dimension, TNode, Tree};
+use std::mem::size_of;
+
+///Shape and memory diagnostics of a tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TreeStats {
+    ///Number of nodes, including the root.
+    pub nodes: usize,
+    ///Number of nodes without children.
+    pub leaves: usize,
+    ///Depth of the deepest leaf. The root is at depth 0.
+    pub max_depth: usize,
+    ///Average depth of the leaves.
+    pub average_depth: f32,
+    ///Number of stored items at each depth.
+    pub items_per_depth: Vec<usize>,
+    ///Number of stored item copies, counting every node an item was pushed to.
+    pub stored: usize,
+    ///Number of unique items in the tree.
+    pub unique: usize,
+    ///Stored copies per unique item.
+    pub duplication: f32,
+    ///Number of leaves that reached the depth limit and hold more items than their capacity.
+    pub saturated: usize,
+    ///Approximate number of bytes the tree has allocated on the heap.
+    pub heap_bytes: usize,
+}
+
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Tree<I, S, D, TNode<I, S, D>> {
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats {
+            unique: self.count,
+            ..Default::default()
+        };
+        let mut leaf_depths = 0;
+        self.prime.visit(0, &mut |node, depth| {
+            stats.nodes += 1;
+            if stats.items_per_depth.len() <= depth {
+                stats.items_per_depth.resize(depth + 1, 0);
+            }
+            stats.items_per_depth[depth] += node.objects.len();
+            stats.stored += node.objects.len();
+            stats.heap_bytes += node.objects.capacity() * size_of::<(I, D)>();
+            match &node.nodes {
+                Some(nodes) => stats.heap_bytes += size_of::<Vec<TNode<I, S, D>>>() + nodes.capacity() * size_of::<TNode<I, S, D>>(),
+                None => {
+                    stats.leaves += 1;
+                    stats.max_depth = stats.max_depth.max(depth);
+                    leaf_depths += depth;
+                    if node.depth_limit == 0 && node.objects.len() > node.capacity as usize {
+                        stats.saturated += 1;
+                    }
+                }
+            }
+        });
+        stats.average_depth = leaf_depths as f32 / stats.leaves as f32;
+        if stats.unique > 0 {
+            stats.duplication = stats.stored as f32 / stats.unique as f32;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use crate::*;
+
+    #[test]
+    fn stats() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        let empty = quadtree.stats();
+        assert_eq!((empty.nodes, empty.leaves, empty.max_depth, empty.stored), (1, 1, 0, 0));
+
+        for x in -50..50 {
+            for y in -50..50 {
+                quadtree.insert(x * 1009 + y * 1013, Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5));
+            }
+        }
+        let stats = quadtree.stats();
+        assert_eq!(stats.nodes, (stats.leaves * 4 - 1) / 3);
+        assert_eq!(stats.unique, quadtree.count());
+        assert_eq!(stats.items_per_depth.iter().sum::<usize>(), stats.stored);
+        assert!(stats.max_depth as f32 >= stats.average_depth);
+        assert!(stats.duplication >= 1.0);
+        assert_eq!(stats.saturated, 0);
+        assert!(stats.heap_bytes > stats.stored * std::mem::size_of::<(i32, Quad)>());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{Dimension, Quad, QuadTree};
+use std::fmt::Write;
+
+///Settings for rendering a quadtree as an SVG document.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgOptions {
+    ///Width of the image in pixels. The height follows the aspect ratio of the root node.
+    pub width: f32,
+    ///Draw the bounds of every stored item.
+    pub items: bool,
+    ///Highlight the query area along with the cells and items a search for it would visit.
+    pub query: Option<Quad>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            width: 1024.0,
+            items: true,
+            query: None,
+        }
+    }
+}
+
+impl<I: Clone> QuadTree<I> {
+    ///Render every node's space as a rectangle, with leaves shaded by how full they are.
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let root = &self.prime.space;
+        let height = options.width * root.half_y / root.half_x;
+        let stroke = root.half_x * 2.0 / options.width;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            options.width,
+            height,
+            root.x - root.half_x,
+            -root.y - root.half_y,
+            root.half_x * 2.0,
+            root.half_y * 2.0,
+        );
+        let _ = writeln!(svg, r#"<g transform="scale(1,-1)" stroke-width="{}">"#, stroke);
+        let mut items = String::new();
+        self.prime.visit(0, &mut |node, _| {
+            let visited = options.query.is_some_and(|query| node.space.overlaps(&query));
+            let color = if visited { "blue" } else { "black" };
+            match node.nodes {
+                Some(_) => rect(&mut svg, &node.space, "none", 0.0, color),
+                None => {
+                    let occupancy = (node.objects.len() as f32 / node.capacity.max(1) as f32).min(1.0);
+                    rect(&mut svg, &node.space, "red", occupancy * 0.6, color);
+                }
+            }
+            if options.items {
+                for (_, item) in node.objects.iter() {
+                    rect(&mut items, item, "none", 0.0, if visited { "blue" } else { "green" });
+                }
+            }
+        });
+        svg.push_str(&items);
+        if let Some(query) = &options.query {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="blue" fill-opacity="0.1" stroke="blue" stroke-dasharray="{}"/>"#,
+                query.x - query.half_x,
+                query.y - query.half_y,
+                query.half_x * 2.0,
+                query.half_y * 2.0,
+                stroke * 4.0,
+            );
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+}
+
+fn rect(svg: &mut String, quad: &Quad, fill: &str, opacity: f32, stroke: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}" stroke="{}"/>"#,
+        quad.x - quad.half_x,
+        quad.y - quad.half_y,
+        quad.half_x * 2.0,
+        quad.half_y * 2.0,
+        fill,
+        opacity,
+        stroke,
+    );
+}
+
+#[cfg(test)]
+mod svg_tests {
+    use crate::*;
+
+    #[test]
+    fn to_svg() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        for x in -10..10 {
+            for y in -10..10 {
+                quadtree.insert(x * 1009 + y * 1013, Quad::new(x as f32 * 50.0 + 2.5, y as f32 * 50.0 + 2.5, 0.5, 0.5));
+            }
+        }
+        let stats = quadtree.stats();
+        let plain = quadtree.to_svg(&SvgOptions { items: false, ..Default::default() });
+        assert!(plain.starts_with("<svg"));
+        assert!(plain.trim_end().ends_with("</svg>"));
+        assert_eq!(plain.matches("<rect").count(), stats.nodes);
+        assert!(!plain.contains("stroke=\"blue\""));
+
+        let query = quadtree.to_svg(&SvgOptions { query: Some(Quad::new(0.0, 0.0, 50.0, 50.0)), ..Default::default() });
+        let items = quadtree.search(&Quad::new(0.0, 0.0, 500.0, 500.0)).len();
+        assert_eq!(query.matches("<rect").count(), stats.nodes + items + 1);
+        assert!(query.contains("stroke=\"blue\""));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Dimension, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
+use crate::{Dimension, Extents, Shape, TNode, Tree, DEFAULT_CAPACITY, DEFAULT_MAX_DEPTH};
 
 ///4-dimensional tesseract.
 #[derive(Debug, Clone, Copy)]
@@ -44,15 +44,41 @@ impl Dimension<16> for Tess {
         || (self.z - area.z).abs() > (self.half_z + area.half_z)
         || (self.w - area.w).abs() > (self.half_w + area.half_w))
     }
+    fn contains(&self, other: &Self) -> bool {
+        (self.x - other.x).abs() + other.half_x <= self.half_x
+            && (self.y - other.y).abs() + other.half_y <= self.half_y
+            && (self.z - other.z).abs() + other.half_z <= self.half_z
+            && (self.w - other.w).abs() + other.half_w <= self.half_w
+    }
+    fn union(&self, other: &Self) -> Self {
+        let min_x = (self.x - self.half_x).min(other.x - other.half_x);
+        let max_x = (self.x + self.half_x).max(other.x + other.half_x);
+        let min_y = (self.y - self.half_y).min(other.y - other.half_y);
+        let max_y = (self.y + self.half_y).max(other.y + other.half_y);
+        let min_z = (self.z - self.half_z).min(other.z - other.half_z);
+        let max_z = (self.z + self.half_z).max(other.z + other.half_z);
+        let min_w = (self.w - self.half_w).min(other.w - other.half_w);
+        let max_w = (self.w + self.half_w).max(other.w + other.half_w);
+        Self::new(
+            (min_x + max_x) / 2.0,
+            (min_y + max_y) / 2.0,
+            (min_z + max_z) / 2.0,
+            (min_w + max_w) / 2.0,
+            (max_x - min_x) / 2.0,
+            (max_y - min_y) / 2.0,
+            (max_z - min_z) / 2.0,
+            (max_w - min_w) / 2.0,
+        )
+    }
     fn contains_center(&self, point: &Self) -> bool {
         !( point.x >= self.x + self.half_x
         || point.y >= self.y + self.half_y
         || point.z >= self.z + self.half_z
         || point.w >= self.w + self.half_w
-        || point.x <= self.x - self.half_x
-        || point.y <= self.y - self.half_y
-        || point.z <= self.z - self.half_z
-        || point.w <= self.w - self.half_w)
+        || point.x < self.x - self.half_x
+        || point.y < self.y - self.half_y
+        || point.z < self.z - self.half_z
+        || point.w < self.w - self.half_w)
     }
     fn subdivisions(&self) -> [Self; 16] {
 
@@ -237,6 +263,28 @@ impl Dimension<16> for Tess {
     }
 }
 
+impl Extents for Tess {
+    const AXES: usize = 4;
+    fn center(&self, axis: usize) -> f32 {
+        [self.x, self.y, self.z, self.w][axis]
+    }
+    fn half(&self, axis: usize) -> f32 {
+        [self.half_x, self.half_y, self.half_z, self.half_w][axis]
+    }
+    fn from_extents(center: &[f32], half: &[f32]) -> Self {
+        Self::new(center[0], center[1], center[2], center[3], half[0], half[1], half[2], half[3])
+    }
+}
+
+impl Shape<Tess> for Tess {
+    fn bounds(&self) -> Tess {
+        *self
+    }
+    fn overlaps_cell(&self, cell: &Tess) -> bool {
+        self.overlaps(cell)
+    }
+}
+
 ///4-dimensional tree representation.
 pub type TessTree<I> = Tree<I, 16, Tess, TNode<I, 16, Tess>>;
 
@@ -0,0 +1,7 @@
+///Sorted ids of the found items, listing an item once however many leaves reported it.
+pub(crate) fn ids<I: Ord, T>(found: Vec<(I, T)>) -> Vec<I> {
+    let mut ids = found.into_iter().map(|(id, _)| id).collect::<Vec<I>>();
+    ids.sort();
+    ids.dedup();
+    ids
+}
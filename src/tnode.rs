@@ -1,35 +1,175 @@
-use crate::{Dimension, Node};
+use crate::{validate::tiles, Dimension, Node, QueryMode, Shape, Violation};
 use std::convert::TryInto;
 
+///Where a node keeps an item that overlaps more than one of its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Insertion {
+    ///Push a copy of the item into every child it overlaps, so only leaves hold items.
+    Duplicate,
+    ///Keep the item in the smallest node that fully contains it, so every item is stored once.
+    Smallest,
+}
+
 #[derive(Debug, Clone)]
-pub struct TNode<I: Clone, const S: usize, D: Clone + Dimension<S>>
+pub struct TNode<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D> = D>
 {
-    space: D,
-    count: u32,
-    capacity: u8,
-    depth_limit: u8,
-    nodes: Option<Box<Vec<Self>>>,
-    objects: Vec<(I, D)>,
+    pub(crate) space: D,
+    pub(crate) count: u32,
+    ///Number of items counted by this node and the nodes below it.
+    pub(crate) total: u32,
+    pub(crate) capacity: u8,
+    pub(crate) depth_limit: u8,
+    pub(crate) insertion: Insertion,
+    pub(crate) nodes: Option<Box<Vec<Self>>>,
+    pub(crate) objects: Vec<(I, T)>,
 }
 
-impl<I: Clone, const S: usize, D: Clone + Dimension<S>> TNode<I, S, D>
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> TNode<I, S, D, T>
 {
     pub fn new(rect: D, capacity: u8, depth: u8) -> Self {
+        Self::with_insertion(rect, capacity, depth, Insertion::Duplicate)
+    }
+    pub fn with_insertion(rect: D, capacity: u8, depth: u8, insertion: Insertion) -> Self {
         Self {
             space: rect,
             count: 0,
+            total: 0,
             capacity,
             depth_limit: depth,
+            insertion,
             nodes: None,
             objects: Vec::with_capacity(capacity as usize),
         }
     }
 
-    fn push_to(&mut self, id: I, rect: D) -> bool {
+    ///Whether the point lies in the node, counting lower edges but not upper ones unless they are also the root's.
+    ///Every point of the root then lies in exactly one leaf, which lets one of the leaves an item was copied into report it.
+    pub(crate) fn holds<F>(&self, root: &D, point: F) -> bool
+    where
+        F: Fn(usize) -> f32,
+    {
+        (0..D::AXES).all(|axis| {
+            let p = point(axis);
+            self.space.min(axis) <= p && (p < self.space.max(axis) || (p >= root.max(axis) && p <= self.space.max(axis)))
+        })
+    }
+
+    ///Visit this node and every node below it depth first, passing along how far below this node each one is.
+    pub(crate) fn visit<F>(&self, depth: usize, f: &mut F)
+    where
+        F: FnMut(&Self, usize),
+    {
+        f(self, depth);
+        if let Some(nodes) = &self.nodes {
+            for node in nodes.iter() {
+                node.visit(depth + 1, f);
+            }
+        }
+    }
+
+    ///Collect the items whose bounds stand in the mode's relation to the area. A copied item is only pushed by the leaf holding
+    ///the lowest corner of where it overlaps both the area and the root.
+    fn mode_into(&self, root: &D, area: &D, mode: QueryMode, buffer: &mut Vec<(I, T)>) {
+        if self.space.overlaps(area) {
+            for (id, o) in self.objects.iter() {
+                let bounds = o.bounds();
+                if bounds.relates(area, mode)
+                    && (self.insertion == Insertion::Smallest || self.holds(root, |axis| bounds.min(axis).max(area.min(axis)).max(root.min(axis))))
+                {
+                    buffer.push((id.clone(), o.clone()));
+                }
+            }
+            if let Some(nodes) = &self.nodes {
+                for n in nodes.iter() {
+                    n.mode_into(root, area, mode, buffer);
+                }
+            }
+        }
+    }
+
+    fn validate_at(&self, depth: usize, violations: &mut Vec<Violation<D>>) -> usize {
+        let mut found = match self.insertion {
+            Insertion::Duplicate if self.nodes.is_some() => {
+                if !self.objects.is_empty() {
+                    violations.push(Violation::InternalObjects { depth, space: self.space.clone(), objects: self.objects.len() });
+                }
+                0
+            }
+            Insertion::Duplicate => {
+                for (_, item) in self.objects.iter().filter(|(_, o)| !o.overlaps_cell(&self.space)) {
+                    violations.push(Violation::StrayItem { depth, space: self.space.clone(), item: item.bounds() });
+                }
+                self.objects.iter().filter(|(_, o)| self.space.contains_center(&o.bounds())).count()
+            }
+            Insertion::Smallest => {
+                for (_, item) in self.objects.iter() {
+                    let bounds = item.bounds();
+                    let stored = match depth {
+                        0 => item.overlaps_cell(&self.space),
+                        _ => self.space.contains(&bounds),
+                    };
+                    let fits_child = self.nodes.as_ref().is_some_and(|x| x.iter().any(|n| n.space.contains(&bounds)));
+                    if !stored || fits_child {
+                        violations.push(Violation::StrayItem { depth, space: self.space.clone(), item: bounds });
+                    }
+                }
+                self.objects.len()
+            }
+        };
+        if (self.nodes.is_none() || self.insertion == Insertion::Smallest) && found != self.count as usize {
+            violations.push(Violation::NodeCount { depth, space: self.space.clone(), expected: self.count as usize, found });
+        }
+        if let Some(nodes) = &self.nodes {
+            if self.depth_limit == 0 {
+                violations.push(Violation::DepthLimit { depth, space: self.space.clone() });
+            }
+            if !tiles(&self.space, nodes.iter().map(|n| &n.space)) {
+                violations.push(Violation::Tiling { depth, space: self.space.clone() });
+            }
+            for n in nodes.iter() {
+                if self.depth_limit.checked_sub(1) != Some(n.depth_limit) {
+                    violations.push(Violation::DepthLimit { depth: depth + 1, space: n.space.clone() });
+                }
+                found += n.validate_at(depth + 1, violations);
+            }
+        }
+        if found != self.total as usize {
+            violations.push(Violation::NodeCount { depth, space: self.space.clone(), expected: self.total as usize, found });
+        }
+        found
+    }
+
+    fn insert_smallest(&mut self, id: I, other: T) -> bool {
+        match &mut self.nodes {
+            Some(nodes) => {
+                let bounds = other.bounds();
+                if let Some(n) = nodes.iter_mut().find(|n| n.space.contains(&bounds)) {
+                    n.insert_smallest(id, other);
+                    self.total += 1;
+                    return true;
+                }
+            }
+            None => {
+                if self.objects.len() >= self.capacity.into() && self.depth_limit > 0 {
+                    self.subdivide();
+                    return self.insert_smallest(id, other);
+                }
+            }
+        }
+        self.objects.push((id, other));
+        self.count += 1;
+        self.total += 1;
+        true
+    }
+
+    fn push_to(&mut self, id: I, rect: T) -> bool {
         match &mut self.nodes {
             Some(nodes) => {
                 let mut b = false;
                 nodes.iter_mut().for_each(|n| b = b | n.insert(id.clone(), rect.clone()));
+                if b {
+                    self.total += 1;
+                }
                 b
             }
             None => false,
@@ -37,15 +177,19 @@ impl<I: Clone, const S: usize, D: Clone + Dimension<S>> TNode<I, S, D>
     }
 }
 
-impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for TNode<I, S, D> {
-    fn insert(&mut self, id: I, other: D) -> bool {
-        if !self.space.overlaps(&other) {
+impl<I: Clone, const S: usize, D: Clone + Dimension<S>, T: Shape<D>> Node<I, S, D, T> for TNode<I, S, D, T> {
+    fn insert(&mut self, id: I, other: T) -> bool {
+        if !other.overlaps_cell(&self.space) {
             return false;
         }
+        if self.insertion == Insertion::Smallest {
+            return self.insert_smallest(id, other);
+        }
         if self.depth_limit <= 0 {
             self.objects.push((id, other.clone()));
-            if self.space.contains_center(&other) {
+            if self.space.contains_center(&other.bounds()) {
                 self.count += 1;
+                self.total += 1;
                 return true;
             } else {
                 return false;
@@ -54,7 +198,7 @@ impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for TNode<
         if self.nodes.is_some() {
             return self.push_to(id, other);
         }
-        let contains = self.space.contains_center(&other);
+        let contains = self.space.contains_center(&other.bounds());
         if self.count >= self.capacity.into() && contains {
             self.subdivide();
             return self.push_to(id, other);
@@ -62,6 +206,7 @@ impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for TNode<
         self.objects.push((id, other));
         if contains {
             self.count += 1;
+            self.total += 1;
             return true;
         } else {
             return false;
@@ -72,55 +217,57 @@ impl<I: Clone, const S: usize, D: Clone + Dimension<S>> Node<I, S, D> for TNode<
             self.space
                 .subdivisions()
                 .iter()
-                .map(|d| Self::new(d.clone(), self.capacity, self.depth_limit - 1))
+                .map(|d| Self::with_insertion(d.clone(), self.capacity, self.depth_limit - 1, self.insertion))
                 .collect::<Vec<Self>>()
                 .try_into()
                 .unwrap_or_else(|_| panic!("A node has created an incorrect number of subdivisions")),
         ));
-        for i in 0..self.objects.len() {
-            let o = self.objects[i].clone();
-            self.push_to(o.0, o.1);
-        }
         self.count = 0;
+        self.total = 0;
+        for (id, o) in std::mem::take(&mut self.objects) {
+            match self.insertion {
+                Insertion::Duplicate => self.push_to(id, o),
+                Insertion::Smallest => self.insert_smallest(id, o),
+            };
+        }
     }
-    fn search(&self, area: &D, buffer: &mut Vec<(I, D)>) {
+    fn search(&self, area: &D, buffer: &mut Vec<(I, T)>) {
         if self.space.overlaps(area) {
-            match &self.nodes {
-                Some(x) => {
-                    for i in 0..x.len() {
-                        x[i].search(area, buffer);
-                    }
+            buffer.extend_from_slice(&self.objects);
+            if let Some(x) = &self.nodes {
+                for i in 0..x.len() {
+                    x[i].search(area, buffer);
                 }
-                None => buffer.append(&mut self.objects.clone()),
             }
         }
     }
-    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+    fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, T)>)
     where
         OF: Fn(&D) -> bool,
     {
         if overlaps(&self.space) {
-            match &self.nodes {
-                Some(x) => {
-                    for i in 0..x.len() {
-                        x[i].search_with(overlaps, buffer);
-                    }
+            buffer.extend_from_slice(&self.objects);
+            if let Some(x) = &self.nodes {
+                for i in 0..x.len() {
+                    x[i].search_with(overlaps, buffer);
                 }
-                None => buffer.append(&mut self.objects.clone()),
             }
         }
     }
     fn clear(&mut self) {
-        match &mut self.nodes {
-            Some(x) => {
-                for i in x.iter_mut() {
-                    i.clear();
-                }
-            }
-            None => {
-                self.objects.clear();
-                self.count = 0;
+        self.objects.clear();
+        self.count = 0;
+        self.total = 0;
+        if let Some(x) = &mut self.nodes {
+            for i in x.iter_mut() {
+                i.clear();
             }
         }
     }
+    fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize {
+        self.validate_at(0, violations)
+    }
+    fn search_mode(&self, area: &D, mode: QueryMode, buffer: &mut Vec<(I, T)>) {
+        self.mode_into(&self.space, area, mode, buffer);
+    }
 }
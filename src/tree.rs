@@ -1,62 +1,171 @@
 pub use tree::*;
 
 mod tree {
-    use crate::TNode;
+    use crate::{TNode, Violation};
     use std::marker::PhantomData;
 
-    pub trait Dimension<const S: usize>: Sized {
+    pub trait Extents {
+        ///Number of axes the dimension spans.
+        const AXES: usize;
+        ///Should return the center of the dimension along the axis.
+        fn center(&self, axis: usize) -> f32;
+        ///Should return half of the dimension's size along the axis.
+        fn half(&self, axis: usize) -> f32;
+        ///Should build the dimension from its center and half sizes along every axis.
+        fn from_extents(center: &[f32], half: &[f32]) -> Self;
+        fn min(&self, axis: usize) -> f32 {
+            self.center(axis) - self.half(axis)
+        }
+        fn max(&self, axis: usize) -> f32 {
+            self.center(axis) + self.half(axis)
+        }
+        ///Euclidean distance between the closest points of the two dimensions, which is zero when they overlap.
+        fn distance(&self, other: &Self) -> f32 {
+            (0..Self::AXES)
+                .map(|axis| ((self.center(axis) - other.center(axis)).abs() - self.half(axis) - other.half(axis)).max(0.0).powi(2))
+                .sum::<f32>()
+                .sqrt()
+        }
+        ///Interval of the parameter t, starting at zero, over which the ray origin + t * direction lies inside the dimension. None when the ray misses.
+        fn ray_interval(&self, origin: &[f32], direction: &[f32]) -> Option<(f32, f32)> {
+            let mut near = 0.0f32;
+            let mut far = f32::INFINITY;
+            for axis in 0..Self::AXES {
+                if direction[axis] == 0.0 {
+                    if origin[axis] < self.min(axis) || origin[axis] > self.max(axis) {
+                        return None;
+                    }
+                    continue;
+                }
+                let a = (self.min(axis) - origin[axis]) / direction[axis];
+                let b = (self.max(axis) - origin[axis]) / direction[axis];
+                near = near.max(a.min(b));
+                far = far.min(a.max(b));
+                if near > far {
+                    return None;
+                }
+            }
+            Some((near, far))
+        }
+    }
+
+    ///Geometry of a stored item, which may differ from the dimension of the cells it is stored in. Only the bounds are required, the overlap tests default to comparing them.
+    pub trait Shape<D: Extents>: Clone {
+        ///Should return the smallest dimension enclosing the shape.
+        fn bounds(&self) -> D;
+        ///Should return true when the shape overlaps the cell. Decides which nodes store the item, so it may err on the side of overlapping.
+        fn overlaps_cell(&self, cell: &D) -> bool {
+            let bounds = self.bounds();
+            (0..D::AXES).all(|axis| bounds.min(axis) <= cell.max(axis) && cell.min(axis) <= bounds.max(axis))
+        }
+        ///Should return true when the shape overlaps the query area. Decides which items exact searches return.
+        fn overlaps_query(&self, area: &D) -> bool {
+            self.overlaps_cell(area)
+        }
+    }
+
+    ///Relation an item must have to the area of a query.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum QueryMode {
+        ///Items entirely inside the area.
+        Within,
+        ///Items overlapping the area.
+        Intersects,
+        ///Items enclosing the whole area.
+        Contains,
+    }
+
+    pub trait Dimension<const S: usize>: Sized + Extents + Shape<Self> {
         ///Should return true when the dimensions overlap.
         fn overlaps(&self, other: &Self) -> bool;
-        ///Should return true when the dimension contains the center of the other dim.
+        ///Should return true when the other dim lies entirely inside the dimension.
+        fn contains(&self, other: &Self) -> bool;
+        ///Return true when the dimension lies entirely inside the other dim.
+        fn within(&self, other: &Self) -> bool {
+            other.contains(self)
+        }
+        ///Return true when the dimension stands in the mode's relation to the area.
+        fn relates(&self, area: &Self, mode: QueryMode) -> bool {
+            match mode {
+                QueryMode::Within => self.within(area),
+                QueryMode::Intersects => self.overlaps(area),
+                QueryMode::Contains => self.contains(area),
+            }
+        }
+        ///Should return the smallest dimension enclosing both dimensions.
+        fn union(&self, other: &Self) -> Self;
+        ///Should return true when the dimension contains the center of the other dim, counting its lower edges but not its upper ones so that a center on a shared edge belongs to exactly one subdivision.
         fn contains_center(&self, point: &Self) -> bool;
         ///Should return a vector of the dims subdivisions filled with every permutation of the dim's halfway points and half sizes, which is 2^dims.
         fn subdivisions(&self) -> [Self; S];
     }
 
-    pub trait Node<I, const S: usize, D: Dimension<S>> {
+    ///Smallest dimension enclosing all of the dimensions, or None when there are none.
+    pub(crate) fn enclose<'a, const S: usize, D: Clone + Dimension<S> + 'a>(mut dims: impl Iterator<Item = &'a D>) -> Option<D> {
+        let first = dims.next()?.clone();
+        Some(dims.fold(first, |bounds, d| bounds.union(d)))
+    }
+
+    pub trait Node<I, const S: usize, D: Dimension<S>, T: Shape<D> = D> {
         ///Insert object into the tree. Should return tree if the object was actually inserted (was not already present).
-        fn insert(&mut self, id: I, other: D) -> bool;
+        fn insert(&mut self, id: I, other: T) -> bool;
         ///Subdivide the tree.
         fn subdivide(&mut self);
         ///Search the tree using the same shape that makes up the tree nodes.
-        fn search(&self, area: &D, buffer: &mut Vec<(I, D)>);
+        fn search(&self, area: &D, buffer: &mut Vec<(I, T)>);
         ///Search the tree with custom overlap logic.
-        fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, D)>)
+        fn search_with<OF>(&self, overlaps: &OF, buffer: &mut Vec<(I, T)>)
         where OF: Fn(&D) -> bool;
         ///Clear the tree.
         fn clear(&mut self);
+        ///Check the node's invariants, pushing every violation found into the buffer. Should return the number of items stored in the node.
+        fn validate(&self, violations: &mut Vec<Violation<D>>) -> usize;
+        ///Search the tree for the items whose bounds stand in the mode's relation to the area. Nodes storing copies of an item should report it once.
+        fn search_mode(&self, area: &D, mode: QueryMode, buffer: &mut Vec<(I, T)>) {
+            let mut found = Vec::new();
+            self.search(area, &mut found);
+            buffer.extend(found.into_iter().filter(|(_, item)| item.bounds().relates(area, mode)));
+        }
     }
 
     #[derive(Debug, Clone)]
-    pub struct Tree<I, const S: usize, D: Dimension<S>, N: Node<I, S, D> = TNode<I, S, D>> {
-        prime: N,
-        count: usize,
+    pub struct Tree<I, const S: usize, D: Dimension<S>, N: Node<I, S, D, T> = TNode<I, S, D>, T: Shape<D> = D> {
+        pub(crate) prime: N,
+        pub(crate) count: usize,
         i: PhantomData<I>,
         dim: PhantomData<D>,
+        shape: PhantomData<T>,
     }
 
-    impl<I, const S: usize, D: Dimension<S>, N: Node<I, S, D>> Tree<I, S, D, N> {
+    impl<I, const S: usize, D: Dimension<S>, N: Node<I, S, D, T>, T: Shape<D>> Tree<I, S, D, N, T> {
         pub fn new_tree(prime: N) -> Self {
             Self {
                 prime,
                 count: 0,
                 i: PhantomData,
                 dim: PhantomData,
+                shape: PhantomData,
             }
         }
-        pub fn insert(&mut self, id: I, item: D) -> bool {
-            if self.prime.insert(id, item) {
+        pub fn insert(&mut self, id: I, item: T) -> bool {
+            let inserted = self.prime.insert(id, item);
+            if inserted {
                 self.count += 1;
-                return true;
             }
-            return false;
+            #[cfg(feature = "validate")]
+            self.assert_valid();
+            inserted
         }
-        pub fn search(&self, area: &D) -> Vec<(I, D)> {
+        pub fn search(&self, area: &D) -> Vec<(I, T)> {
             let mut buffer = Vec::with_capacity(self.count);
             self.prime.search(area, &mut buffer);
             buffer.into_iter().collect()
         }
-        pub fn search_custom<OF>(&self, overlaps: &OF) -> Vec<(I, D)>
+        ///Search the tree, keeping only the items whose shape overlaps the area.
+        pub fn search_exact(&self, area: &D) -> Vec<(I, T)> {
+            self.search(area).into_iter().filter(|(_, item)| item.overlaps_query(area)).collect()
+        }
+        pub fn search_custom<OF>(&self, overlaps: &OF) -> Vec<(I, T)>
         where
             OF: Fn(&D) -> bool,
         {
@@ -66,6 +175,9 @@ mod tree {
         }
         pub fn clear(&mut self) {
             self.prime.clear();
+            self.count = 0;
+            #[cfg(feature = "validate")]
+            self.assert_valid();
         }
         pub fn prime(&self) -> &N {
             &self.prime
@@ -74,4 +186,13 @@ mod tree {
             self.count
         }
     }
+
+    impl<I, const S: usize, D: Dimension<S>, N: Node<I, S, D>> Tree<I, S, D, N> {
+        ///Search the tree for the items standing in the mode's relation to the area, each reported once.
+        pub fn search_mode(&self, area: &D, mode: QueryMode) -> Vec<(I, D)> {
+            let mut buffer = Vec::new();
+            self.prime.search_mode(area, mode, &mut buffer);
+            buffer
+        }
+    }
 }
@@ -0,0 +1,114 @@
+use crate::{Dimension, Extents, Node, Shape, Tree};
+use std::fmt;
+
+///A broken tree invariant, along with the depth of the node it was found at.
+#[derive(Debug, Clone)]
+pub enum Violation<D> {
+    ///The node's children don't exactly tile its space.
+    Tiling { depth: usize, space: D },
+    ///A stored item doesn't overlap the leaf holding it.
+    StrayItem { depth: usize, space: D, item: D },
+    ///A node with children still holds objects.
+    InternalObjects { depth: usize, space: D, objects: usize },
+    ///The node's depth limit doesn't follow from its parent's, or the node was subdivided past its depth limit.
+    DepthLimit { depth: usize, space: D },
+    ///A node's bounds don't enclose everything stored below it.
+    Bounds { depth: usize, space: D },
+    ///The node holds more entries than its capacity, or fewer than its minimum.
+    Fill { depth: usize, space: D, entries: usize },
+    ///The node's count doesn't match the objects it holds.
+    NodeCount { depth: usize, space: D, expected: usize, found: usize },
+    ///The tree's count doesn't match the items stored in it.
+    Count { expected: usize, found: usize },
+}
+
+impl<D> fmt::Display for Violation<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tiling { depth, .. } => write!(f, "children of a node at depth {} don't tile its space", depth),
+            Self::StrayItem { depth, .. } => write!(f, "a leaf at depth {} holds an item outside its space", depth),
+            Self::InternalObjects { depth, objects, .. } => write!(f, "a node at depth {} has children but holds {} objects", depth, objects),
+            Self::DepthLimit { depth, .. } => write!(f, "a node at depth {} doesn't respect its depth limit", depth),
+            Self::Bounds { depth, .. } => write!(f, "a node at depth {} has bounds that don't enclose its entries", depth),
+            Self::Fill { depth, entries, .. } => write!(f, "a node at depth {} holds {} entries, outside its allowed fill", depth, entries),
+            Self::NodeCount { depth, expected, found, .. } => write!(f, "a node at depth {} counts {} items but holds {}", depth, expected, found),
+            Self::Count { expected, found } => write!(f, "the tree counts {} items but holds {}", expected, found),
+        }
+    }
+}
+
+impl<I, const S: usize, D: Dimension<S>, N: Node<I, S, D, T>, T: Shape<D>> Tree<I, S, D, N, T> {
+    ///Walk the tree and report every broken invariant.
+    pub fn validate(&self) -> Result<(), Vec<Violation<D>>> {
+        let mut violations = Vec::new();
+        let found = self.prime.validate(&mut violations);
+        if found != self.count {
+            violations.push(Violation::Count { expected: self.count, found });
+        }
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+
+    #[cfg(feature = "validate")]
+    pub(crate) fn assert_valid(&self) {
+        if let Err(violations) = self.validate() {
+            let messages = violations.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+            panic!("tree failed validation: {}", messages.join("; "));
+        }
+    }
+}
+
+///Whether the children are exactly the subdivisions of the space, in order.
+pub(crate) fn tiles<'a, const S: usize, D: Dimension<S> + 'a>(space: &D, children: impl Iterator<Item = &'a D>) -> bool {
+    let children = children.collect::<Vec<&D>>();
+    children.len() == S && children.iter().zip(space.subdivisions().iter()).all(|(child, d)| same(*child, d))
+}
+
+///Whether both dimensions have exactly the same extents.
+pub(crate) fn same<D: Extents>(a: &D, b: &D) -> bool {
+    (0..D::AXES).all(|axis| a.center(axis) == b.center(axis) && a.half(axis) == b.half(axis))
+}
+
+///Whether the outer dimension holds the inner one, allowing for the rounding of repeated unions.
+pub(crate) fn encloses<D: Extents>(outer: &D, inner: &D) -> bool {
+    (0..D::AXES).all(|axis| {
+        let tolerance = 1e-4 * outer.half(axis).abs().max(outer.center(axis).abs()).max(1.0);
+        inner.min(axis) >= outer.min(axis) - tolerance && inner.max(axis) <= outer.max(axis) + tolerance
+    })
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use crate::*;
+
+    #[test]
+    fn validate() {
+        let mut quadtree = QuadTree::new(Quad::new(0.0, 0.0, 500.0, 500.0));
+        for x in -50..50 {
+            for y in -50..50 {
+                quadtree.insert(x * 1009 + y * 1013, Quad::new(x as f32 * 10.0 + 2.5, y as f32 * 10.0 + 2.5, 0.5, 0.5));
+            }
+        }
+        assert_eq!(quadtree.count(), 10000);
+        assert!(quadtree.validate().is_ok());
+        quadtree.clear();
+        assert!(quadtree.validate().is_ok());
+
+        let mut broken = quadtree.clone();
+        broken.count = 3;
+        assert!(matches!(broken.validate().unwrap_err()[..], [Violation::Count { expected: 3, found: 0 }]));
+
+        let mut broken = quadtree.clone();
+        broken.prime.objects.push((0, Quad::new(600.0, 0.0, 0.5, 0.5)));
+        broken.prime.nodes.as_mut().unwrap()[0].depth_limit = 0;
+        let violations = broken.validate().unwrap_err();
+        assert!(violations.iter().any(|v| matches!(v, Violation::InternalObjects { depth: 0, objects: 1, .. })));
+        assert!(violations.iter().any(|v| matches!(v, Violation::DepthLimit { depth: 1, .. })));
+
+        let mut broken = quadtree.clone();
+        broken.prime.nodes.as_mut().unwrap()[1].space.x += 1.0;
+        assert!(broken.validate().unwrap_err().iter().any(|v| matches!(v, Violation::Tiling { depth: 0, .. })));
+    }
+}